@@ -0,0 +1,141 @@
+//! Compares narrowing a search with the inverted index against checking
+//! every snippet's words, on a synthetic vault.
+//!
+//! ```text
+//! cargo bench --bench search_index
+//! SNIPPETS=20000 cargo bench --bench search_index
+//! ```
+//!
+//! Both approaches must find the same snippets; the benchmark panics if
+//! they ever disagree.
+
+// The app's modules are pulled in by path since it is a binary crate; the
+// benchmark only uses part of them.
+#![allow(dead_code)]
+
+#[path = "../src/search/index.rs"]
+mod index;
+#[path = "../src/search/words.rs"]
+mod words;
+
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use index::SearchIndex;
+use words::{quality, words};
+
+const QUERIES: &[&str] = &["async", "hndlr", "parse json", "reqest", "vec push", "cachenode1234", "zzzz"];
+const ROUNDS: u32 = 20;
+
+struct Document {
+    id: String,
+    /// Title, description and tags.
+    prose: Vec<String>,
+    code: String,
+}
+
+fn main() {
+    let count = std::env::var("SNIPPETS").ok().and_then(|n| n.parse().ok()).unwrap_or(5_000);
+    let documents = vault(count);
+
+    let started = Instant::now();
+    let mut index = SearchIndex::default();
+    for document in &documents {
+        index.insert(&document.id, document.prose.iter().map(String::as_str), [document.code.as_str()]);
+    }
+    println!("{count} snippets, indexed in {:.1?}", started.elapsed());
+
+    let started = Instant::now();
+    let edited = &documents[count / 2];
+    index.insert(&edited.id, ["renamed request handler"], []);
+    index.insert(&edited.id, edited.prose.iter().map(String::as_str), [edited.code.as_str()]);
+    println!("re-indexing one edited snippet took {:.1?}", started.elapsed() / 2);
+
+    println!("{:<14} {:>8} {:>12} {:>12} {:>9}", "query", "matches", "scan", "index", "speedup");
+    for query in QUERIES {
+        let expected = scan(&documents, query);
+        let found: HashSet<&str> = index.candidates(query).unwrap_or_default();
+        assert_eq!(found, expected, "index and scan disagree on {query:?}");
+
+        let scan_time = time(|| scan(&documents, query).len());
+        let index_time = time(|| index.candidates(query).map_or(0, |ids| ids.len()));
+        println!(
+            "{:<14} {:>8} {:>12.1?} {:>12.1?} {:>8.1}x",
+            format!("{query:?}"),
+            expected.len(),
+            scan_time,
+            index_time,
+            scan_time.as_secs_f64() / index_time.as_secs_f64(),
+        );
+    }
+}
+
+/// Every document matching each word of `query` the way the search scores
+/// it, found by re-splitting every document's text: any match against a
+/// title, description or tag word, or appearing verbatim in the code.
+fn scan<'a>(documents: &'a [Document], query: &str) -> HashSet<&'a str> {
+    let query_words = words(query);
+    documents
+        .iter()
+        .filter(|document| {
+            let prose_words: Vec<String> = document.prose.iter().flat_map(|t| words(t)).collect();
+            let code = document.code.to_lowercase();
+            query_words.iter().all(|word| {
+                prose_words.iter().any(|candidate| quality(word, candidate, true).is_some()) || code.contains(word.as_str())
+            })
+        })
+        .map(|document| document.id.as_str())
+        .collect()
+}
+
+/// Mean time of `f` over [`ROUNDS`] runs.
+fn time(mut f: impl FnMut() -> usize) -> Duration {
+    let started = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(f());
+    }
+    started.elapsed() / ROUNDS
+}
+
+/// `count` snippet-like documents drawn from a fixed vocabulary of common
+/// programming words plus generated identifiers, the same on every run.
+fn vault(count: usize) -> Vec<Document> {
+    const COMMON: &[&str] = &[
+        "fn", "let", "const", "async", "await", "return", "import", "from", "class", "def",
+        "self", "this", "new", "vec", "push", "map", "filter", "result", "error", "request",
+        "response", "handler", "parse", "json", "string", "list", "dict", "query", "select",
+        "where", "state", "hook", "component", "render", "props", "value", "index", "loop",
+    ];
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move |bound: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % bound as u64) as usize
+    };
+    let identifiers: Vec<String> = (0..2_000)
+        .map(|i| {
+            let stem = COMMON[next(COMMON.len())];
+            format!("{stem}{}{i}", ["Item", "Data", "Cache", "Node"][next(4)])
+        })
+        .collect();
+    let word = |next: &mut dyn FnMut(usize) -> usize| -> String {
+        if next(3) == 0 {
+            identifiers[next(identifiers.len())].clone()
+        } else {
+            COMMON[next(COMMON.len())].to_string()
+        }
+    };
+
+    (0..count)
+        .map(|i| {
+            let mut text = |words: usize| (0..words).map(|_| word(&mut next)).collect::<Vec<_>>().join(" ");
+            let title = text(4);
+            let description = text(10);
+            let code = (0..15).map(|_| text(8)).collect::<Vec<_>>().join(";\n");
+            let tags = text(3);
+            Document { id: format!("snippet-{i}"), prose: vec![title, description, tags], code }
+        })
+        .collect()
+}
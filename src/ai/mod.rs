@@ -0,0 +1,121 @@
+//! AI explanations of snippets behind a swappable provider.
+//!
+//! The UI only talks to [`ExplainProvider`]. The shipped implementation,
+//! [`OpenAiCompatible`], speaks the OpenAI chat completions protocol, which a
+//! hosted API, a local Ollama or llama.cpp server, or a mock server all
+//! understand; only the base URL in [`ExplainConfig`] differs.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+mod openai;
+
+pub use openai::OpenAiCompatible;
+
+/// What a provider is asked to explain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplainRequest {
+    pub title: String,
+    pub language: String,
+    pub code: String,
+}
+
+/// Not `Send`: on the web target requests run on the browser's event loop.
+pub type ExplainFuture = Pin<Box<dyn Future<Output = Result<String, ExplainError>>>>;
+
+pub trait ExplainProvider {
+    fn explain(&self, request: ExplainRequest) -> ExplainFuture;
+}
+
+/// An explanation saved on its snippet so revisits don't hit the provider.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedExplanation {
+    pub text: String,
+    /// [`content_hash`] of the code the explanation was generated for.
+    pub content_hash: u64,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl CachedExplanation {
+    /// True once the snippet's code or language no longer matches what was explained.
+    pub fn is_stale(&self, language: &str, code: &str) -> bool {
+        self.content_hash != content_hash(language, code)
+    }
+}
+
+/// A hash of `language` and `code` that is stable across builds and
+/// platforms (64-bit FNV-1a), unlike `std`'s `DefaultHasher`.
+pub fn content_hash(language: &str, code: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    // The separator keeps ("ab", "c") and ("a", "bc") apart.
+    [language.as_bytes(), &[0], code.as_bytes()]
+        .concat()
+        .iter()
+        .fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+/// Where explanations come from, edited on the settings screen.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExplainConfig {
+    /// Base URL of an OpenAI-compatible API, e.g. `https://api.openai.com/v1`
+    /// or `http://localhost:11434/v1` for Ollama. Empty disables AI Explain.
+    pub base_url: String,
+    pub model: String,
+    /// Sent as a bearer token when set. Local servers usually need none.
+    pub api_key: Option<String>,
+}
+
+impl Default for ExplainConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:11434/v1".into(),
+            model: "llama3.2".into(),
+            api_key: None,
+        }
+    }
+}
+
+impl ExplainConfig {
+    pub fn provider(&self) -> Result<Box<dyn ExplainProvider>, ExplainError> {
+        if self.base_url.trim().is_empty() {
+            return Err(ExplainError::NotConfigured);
+        }
+        Ok(Box::new(OpenAiCompatible::new(self.clone())))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExplainError {
+    NotConfigured,
+    /// The server could not be reached.
+    Network(String),
+    /// The server answered with an error status.
+    Status(u16, String),
+    /// The server answered, but not with an explanation.
+    BadResponse(String),
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExplainError::NotConfigured => {
+                write!(f, "AI Explain is not configured; set a provider URL in Settings")
+            }
+            ExplainError::Network(msg) => write!(f, "could not reach the AI provider: {msg}"),
+            ExplainError::Status(status, body) => {
+                write!(f, "the AI provider returned HTTP {status}: {body}")
+            }
+            ExplainError::BadResponse(msg) => {
+                write!(f, "unexpected response from the AI provider: {msg}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExplainError {}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ExplainConfig, ExplainError, ExplainFuture, ExplainProvider, ExplainRequest};
+
+const SYSTEM_PROMPT: &str = "You are a senior engineer explaining code snippets to teammates. \
+    Say what the snippet does, walk through how it works, and point out any pitfalls. \
+    Be concise and use plain prose.";
+
+/// A provider speaking the `/chat/completions` endpoint of the OpenAI API.
+pub struct OpenAiCompatible {
+    config: ExplainConfig,
+    client: reqwest::Client,
+}
+
+impl OpenAiCompatible {
+    pub fn new(config: ExplainConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: [Message<'a>; 2],
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+impl ExplainProvider for OpenAiCompatible {
+    fn explain(&self, request: ExplainRequest) -> ExplainFuture {
+        let url = format!("{}/chat/completions", self.config.base_url.trim_end_matches('/'));
+        let prompt = format!(
+            "Explain this {language} snippet titled \"{title}\":\n\n```{language}\n{code}\n```",
+            language = request.language,
+            title = request.title,
+            code = request.code,
+        );
+        let body = ChatRequest {
+            model: &self.config.model,
+            messages: [
+                Message { role: "system", content: SYSTEM_PROMPT },
+                Message { role: "user", content: &prompt },
+            ],
+        };
+        let mut http = self.client.post(url).json(&body);
+        if let Some(key) = self.config.api_key.as_deref().filter(|key| !key.is_empty()) {
+            http = http.bearer_auth(key);
+        }
+
+        Box::pin(async move {
+            let response = http
+                .send()
+                .await
+                .map_err(|err| ExplainError::Network(err.to_string()))?;
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(ExplainError::Status(status.as_u16(), body));
+            }
+            let chat: ChatResponse = response
+                .json()
+                .await
+                .map_err(|err| ExplainError::BadResponse(err.to_string()))?;
+            chat.choices
+                .into_iter()
+                .next()
+                .map(|choice| choice.message.content.trim().to_string())
+                .filter(|content| !content.is_empty())
+                .ok_or_else(|| ExplainError::BadResponse("no explanation in the reply".into()))
+        })
+    }
+}
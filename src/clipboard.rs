@@ -0,0 +1,56 @@
+//! Writing snippet code to the system clipboard.
+
+/// Copies `text` to the clipboard, returning a user-presentable reason on
+/// failure (permission denied, no secure context, ...).
+pub async fn write_text(text: String) -> Result<(), String> {
+    platform::write_text(text).await
+}
+
+/// The browser's async Clipboard API.
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    use wasm_bindgen_futures::JsFuture;
+
+    pub async fn write_text(text: String) -> Result<(), String> {
+        let window = web_sys::window().ok_or("no browser window")?;
+        let promise = window.navigator().clipboard().write_text(&text);
+        JsFuture::from(promise).await.map(drop).map_err(|err| {
+            err.as_string()
+                .unwrap_or_else(|| "clipboard access was denied".into())
+        })
+    }
+}
+
+/// On mobile the UI runs in the system webview, so the copy goes through
+/// its clipboard, falling back to `execCommand("copy")` on webviews that
+/// do not expose the async Clipboard API.
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use dioxus::prelude::document;
+
+    const SCRIPT: &str = r#"
+        const text = await dioxus.recv();
+        try {
+            await navigator.clipboard.writeText(text);
+            dioxus.send(null);
+        } catch (err) {
+            const area = document.createElement("textarea");
+            area.value = text;
+            document.body.appendChild(area);
+            area.select();
+            const copied = document.execCommand("copy");
+            area.remove();
+            dioxus.send(copied ? null : String(err));
+        }
+    "#;
+
+    pub async fn write_text(text: String) -> Result<(), String> {
+        let mut eval = document::eval(SCRIPT);
+        eval.send(text).map_err(|err| err.to_string())?;
+        match eval.recv::<Option<String>>().await {
+            Ok(None) => Ok(()),
+            Ok(Some(err)) => Err(err),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Folders for organizing snippets, optionally nested.
+//!
+//! Collections are kept in their own versioned document next to the vault;
+//! a snippet only records the id of the collection it is in.
+
+use std::collections::HashSet;
+
+use dioxus::logger::tracing;
+use serde::{Deserialize, Serialize};
+
+use crate::store::schema::{self, Format};
+use crate::store::{self, Backend, StoreError};
+use crate::Snippet;
+
+/// Changing [`Collection`]'s shape needs a migration step here, as the
+/// vault's does.
+const FORMAT: Format = Format { field: "collections", migrations: &[schema::enveloped] };
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    /// The collection this one is nested in; `None` at the top level.
+    #[serde(default)]
+    pub parent: Option<String>,
+}
+
+/// `parent`'s direct children, sorted by name.
+pub fn children<'a>(collections: &'a [Collection], parent: Option<&str>) -> Vec<&'a Collection> {
+    let mut children: Vec<&Collection> = collections
+        .iter()
+        .filter(|collection| collection.parent.as_deref() == parent)
+        .collect();
+    children.sort_by_key(|collection| collection.name.to_lowercase());
+    children
+}
+
+/// `id` and every collection nested under it, however deep.
+pub fn subtree(collections: &[Collection], id: &str) -> HashSet<String> {
+    let mut found = HashSet::from([id.to_string()]);
+    let mut pending = vec![id.to_string()];
+    while let Some(parent) = pending.pop() {
+        for child in collections.iter().filter(|c| c.parent.as_deref() == Some(parent.as_str())) {
+            if found.insert(child.id.clone()) {
+                pending.push(child.id.clone());
+            }
+        }
+    }
+    found
+}
+
+/// "Work / Backend / Rust" for a collection nested two levels deep.
+pub fn path(collections: &[Collection], id: &str) -> String {
+    let mut names = Vec::new();
+    let mut current = Some(id);
+    while let Some(id) = current {
+        let Some(collection) = collections.iter().find(|c| c.id == id) else {
+            break;
+        };
+        names.push(collection.name.as_str());
+        current = collection.parent.as_deref();
+        // A parent cycle should never be saved, but must not hang the UI.
+        if names.len() > collections.len() {
+            break;
+        }
+    }
+    names.reverse();
+    names.join(" / ")
+}
+
+/// Removes collection `id`. Its sub-collections and snippets move up to its
+/// parent; the snippets that need saving are returned.
+pub fn remove(collections: &mut Vec<Collection>, snippets: &[Snippet], id: &str) -> Vec<Snippet> {
+    let Some(position) = collections.iter().position(|c| c.id == id) else {
+        return Vec::new();
+    };
+    let removed = collections.remove(position);
+    for child in collections.iter_mut().filter(|c| c.parent.as_deref() == Some(id)) {
+        child.parent = removed.parent.clone();
+    }
+    snippets
+        .iter()
+        .filter(|snippet| snippet.collection.as_deref() == Some(id))
+        .map(|snippet| Snippet { collection: removed.parent.clone(), ..snippet.clone() })
+        .collect()
+}
+
+/// The saved collections, none if nothing has been saved yet, or why they
+/// could not be read.
+pub fn load() -> Result<Vec<Collection>, StoreError> {
+    let loaded = match store::default_backend("collections").read() {
+        Ok(Some(raw)) => FORMAT.decode(&raw).map_err(StoreError::from),
+        Ok(None) => Ok(Vec::new()),
+        Err(err) => Err(err),
+    };
+    loaded.inspect_err(|err| tracing::error!("{err}; collections will not be saved this session"))
+}
+
+pub fn save(collections: &[Collection]) {
+    let result = FORMAT.encode(collections)
+        .map_err(|err| StoreError::Backend(err.to_string()))
+        .and_then(|raw| store::default_backend("collections").write(&raw));
+    if let Err(err) = result {
+        tracing::error!("{err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_collections_saved_before_the_envelope() {
+        let legacy = r#"[{"id": "a", "name": "Work"}, {"id": "b", "name": "Rust", "parent": "a"}]"#;
+        let collections: Vec<Collection> = FORMAT.decode(legacy).unwrap();
+        assert_eq!(collections[1].parent.as_deref(), Some("a"));
+        let saved = FORMAT.encode(&collections).unwrap();
+        assert!(saved.contains(r#""version":1"#));
+        assert_eq!(FORMAT.decode::<Vec<Collection>>(&saved).unwrap(), collections);
+    }
+}
@@ -1,27 +1,38 @@
 #![allow(non_snake_case)]
 use dioxus::prelude::*;
-use crate::Snippet;
+use crate::{history, tags, time, AppState, Snippet};
+use super::tag_editor::TagEditor;
 
 #[component]
 pub fn AddEditScreen(snippet: Option<Snippet>, on_save: EventHandler<Snippet>, on_cancel: EventHandler<()>) -> Element {
     let is_editing = snippet.is_some();
+    let now = time::now();
     let initial_snippet = snippet.unwrap_or_else(|| Snippet {
-        id: format!("{}", js_sys::Date::now() as u64),
+        id: Snippet::new_id(),
         title: String::new(),
         language: "rust".into(),
         code: String::new(),
         description: None,
         tags: vec![],
-        created_at: "2024-01-15".into(),
-        updated_at: "2024-01-15".into(),
+        created_at: now,
+        updated_at: now,
         is_favorite: false,
+        explanation: None,
+        deleted_at: None,
+        history: Vec::new(),
+        collection: None,
+        view_count: 0,
+        copy_count: 0,
     });
 
     let mut title = use_signal(|| initial_snippet.title.clone());
     let mut language = use_signal(|| initial_snippet.language.clone());
     let mut code = use_signal(|| initial_snippet.code.clone());
-    let mut description = use_signal(|| initial_snippet.description.unwrap_or_default());
-    let mut tags_input = use_signal(|| initial_snippet.tags.join(", "));
+    let mut description = use_signal(|| initial_snippet.description.clone().unwrap_or_default());
+    let mut tags = use_signal(|| initial_snippet.tags.clone());
+
+    let AppState { store, settings, .. } = use_context();
+    let known_tags: Vec<String> = tags::usage(store.read().list()).into_iter().map(|(tag, _)| tag).collect();
 
     let languages = vec![
         "rust", "javascript", "python", "typescript", "go", "java", 
@@ -39,23 +50,25 @@ pub fn AddEditScreen(snippet: Option<Snippet>, on_save: EventHandler<Snippet>, o
                     class: "snippet-form",
                     onsubmit: move |e| {
                         e.prevent_default();
-                        let tags: Vec<String> = tags_input()
-                            .split(',')
-                            .map(|s| s.trim().to_string())
-                            .filter(|s| !s.is_empty())
-                            .collect();
-
-                        let new_snippet = Snippet {
+                        let mut new_snippet = Snippet {
                             id: initial_snippet.id.clone(),
                             title: title(),
                             language: language(),
                             code: code(),
                             description: if description().is_empty() { None } else { Some(description()) },
-                            tags,
-                            created_at: initial_snippet.created_at.clone(),
-                            updated_at: "2024-01-15".into(),
+                            tags: tags(),
+                            created_at: initial_snippet.created_at,
+                            updated_at: time::now(),
                             is_favorite: initial_snippet.is_favorite,
+                            // Kept even if the code changed; the detail view flags it as stale.
+                            explanation: initial_snippet.explanation.clone(),
+                            deleted_at: initial_snippet.deleted_at,
+                            history: initial_snippet.history.clone(),
+                            collection: initial_snippet.collection.clone(),
+                            view_count: initial_snippet.view_count,
+                            copy_count: initial_snippet.copy_count,
                         };
+                        history::record_save(is_editing.then_some(&initial_snippet), &mut new_snippet);
                         on_save.call(new_snippet);
                     },
 
@@ -111,13 +124,12 @@ pub fn AddEditScreen(snippet: Option<Snippet>, on_save: EventHandler<Snippet>, o
                     }
 
                     div { class: "form-group",
-                        label { class: "form-label", "Tags (comma separated)" }
-                        input {
-                            class: "form-input",
-                            r#type: "text",
-                            placeholder: "e.g., beginner, algorithm, api",
-                            value: "{tags_input}",
-                            oninput: move |e| tags_input.set(e.value())
+                        label { class: "form-label", "Tags" }
+                        TagEditor {
+                            tags: tags(),
+                            known_tags,
+                            rules: settings.read().tag_rules.clone(),
+                            on_change: move |updated: Vec<String>| tags.set(updated),
                         }
                     }
 
@@ -5,23 +5,48 @@ use dioxus::prelude::*;
 #[component]
 pub fn AIExplain(
     explanation: Option<String>,
+    /// The explanation was generated for an earlier version of the code.
+    stale: bool,
     loading: bool,
-    on_request: EventHandler<()>
+    error: Option<String>,
+    on_request: EventHandler<()>,
+    on_cancel: EventHandler<()>,
 ) -> Element {
     rsx! {
         div { class: "ai-section",
-            button {
-                class: "btn btn-ai",
-                onclick: move |_| on_request.call(()),
-                disabled: loading,
-                span { class: "btn-icon", "🤖" }
-                if loading { "Analyzing..." } else { "AI Explain" }
+            div { class: "ai-controls",
+                button {
+                    class: "btn btn-ai",
+                    onclick: move |_| on_request.call(()),
+                    disabled: loading,
+                    span { class: "btn-icon", "🤖" }
+                    if loading {
+                        "Analyzing..."
+                    } else if explanation.is_some() {
+                        "Regenerate"
+                    } else {
+                        "AI Explain"
+                    }
+                }
+                if loading {
+                    button {
+                        class: "btn btn-ghost",
+                        onclick: move |_| on_cancel.call(()),
+                        "Cancel"
+                    }
+                }
+            }
+            if let Some(err) = error {
+                div { class: "ai-error", "{err}" }
             }
             if let Some(expl) = explanation {
                 div { class: "ai-explanation",
                     div { class: "ai-header",
                         span { class: "ai-icon", "🤖" }
                         "AI Explanation"
+                        if stale {
+                            span { class: "ai-stale", "Outdated: the code has changed since" }
+                        }
                     }
                     p { class: "ai-text", "{expl}" }
                 }
@@ -29,18 +54,3 @@ pub fn AIExplain(
         }
     }
 }
-
-/// A simple version used in SnippetCard.
-#[component]
-pub fn AiExplainButton() -> Element {
-    rsx! {
-        button {
-            class: "mt-2 bg-purple-600 hover:bg-purple-800 px-3 py-1 text-sm rounded",
-            onclick: move |_| {
-                println!("🤖  AI explain clicked – integrate GPT here");
-            },
-            "🤖 Explain with AI"
-        }
-    }
-}
-
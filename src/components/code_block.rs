@@ -0,0 +1,21 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use crate::highlight::highlight;
+use crate::AppState;
+
+/// A syntax-highlighted code block in the user's code theme.
+#[component]
+pub fn CodeBlock(code: String, language: String) -> Element {
+    let theme = use_context::<AppState>().settings.read().code_theme;
+
+    rsx! {
+        pre { class: "code-block {theme.class()} {language}",
+            code {
+                for token in highlight(&language, &code) {
+                    span { class: token.kind.class(), "{token.text}" }
+                }
+            }
+        }
+    }
+}
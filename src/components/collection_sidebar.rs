@@ -0,0 +1,186 @@
+#![allow(non_snake_case)]
+use std::collections::{HashMap, HashSet};
+
+use dioxus::prelude::*;
+
+use crate::collections::{self, Collection};
+
+/// Browses collections as a tree and scopes the home screen to one of them.
+#[component]
+pub fn CollectionSidebar(
+    collections: Vec<Collection>,
+    /// Snippets in each collection, counting nested collections too.
+    counts: HashMap<String, usize>,
+    total: usize,
+    selected: Option<String>,
+    on_select: EventHandler<Option<String>>,
+    /// A new collection's name and the collection it goes in.
+    on_create: EventHandler<(String, Option<String>)>,
+    on_delete: EventHandler<String>,
+) -> Element {
+    // Where a new collection is being added: `Some(None)` at the top level.
+    let mut adding_to = use_signal(|| None::<Option<String>>);
+    let mut new_name = use_signal(String::new);
+    let collapsed = use_signal(HashSet::<String>::new);
+
+    let adding_label = match adding_to() {
+        Some(Some(parent)) => format!("New collection in {}", collections::path(&collections, &parent)),
+        _ => "New collection".to_string(),
+    };
+
+    rsx! {
+        aside { class: "collection-sidebar",
+            div { class: "sidebar-header",
+                h3 { class: "form-section-title", "Collections" }
+                button {
+                    class: "btn btn-ghost btn-sm",
+                    title: "New collection",
+                    onclick: move |_| adding_to.set(Some(None)),
+                    "+"
+                }
+            }
+
+            button {
+                class: if selected.is_none() { "collection-row active" } else { "collection-row" },
+                onclick: move |_| on_select.call(None),
+                span { class: "collection-name", "All snippets" }
+                span { class: "collection-count", "{total}" }
+            }
+
+            CollectionTree {
+                collections: collections.clone(),
+                parent: None,
+                depth: 0,
+                counts,
+                selected: selected.clone(),
+                collapsed,
+                on_select,
+                on_add_child: move |id: String| adding_to.set(Some(Some(id))),
+                on_delete,
+            }
+
+            if let Some(parent) = adding_to() {
+                form {
+                    class: "new-collection-form",
+                    onsubmit: move |e| {
+                        e.prevent_default();
+                        let name = new_name().trim().to_string();
+                        if !name.is_empty() {
+                            on_create.call((name, parent.clone()));
+                        }
+                        new_name.set(String::new());
+                        adding_to.set(None);
+                    },
+                    input {
+                        class: "form-input",
+                        r#type: "text",
+                        placeholder: "{adding_label}",
+                        value: "{new_name}",
+                        onmounted: move |e| async move {
+                            let _ = e.set_focus(true).await;
+                        },
+                        oninput: move |e| new_name.set(e.value()),
+                    }
+                    button { class: "btn btn-primary btn-sm", r#type: "submit", "Add" }
+                    button {
+                        class: "btn btn-ghost btn-sm",
+                        r#type: "button",
+                        onclick: move |_| adding_to.set(None),
+                        "Cancel"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The children of `parent`, each followed by its own subtree unless collapsed.
+#[component]
+fn CollectionTree(
+    collections: Vec<Collection>,
+    parent: Option<String>,
+    depth: usize,
+    counts: HashMap<String, usize>,
+    selected: Option<String>,
+    collapsed: Signal<HashSet<String>>,
+    on_select: EventHandler<Option<String>>,
+    on_add_child: EventHandler<String>,
+    on_delete: EventHandler<String>,
+) -> Element {
+    let children: Vec<Collection> = collections::children(&collections, parent.as_deref())
+        .into_iter()
+        .cloned()
+        .collect();
+    let indent = format!("padding-left: {}rem", 0.75 + depth as f32);
+
+    rsx! {
+        for collection in children {
+            div { key: "{collection.id}",
+                div {
+                    class: if selected.as_deref() == Some(collection.id.as_str()) { "collection-row active" } else { "collection-row" },
+                    style: "{indent}",
+                    onclick: {
+                        let id = collection.id.clone();
+                        move |_| on_select.call(Some(id.clone()))
+                    },
+                    if !collections::children(&collections, Some(&collection.id)).is_empty() {
+                        button {
+                            class: "collection-toggle",
+                            onclick: {
+                                let id = collection.id.clone();
+                                move |e: MouseEvent| {
+                                    e.stop_propagation();
+                                    let mut collapsed = collapsed;
+                                    let mut set = collapsed.write();
+                                    if !set.remove(&id) {
+                                        set.insert(id.clone());
+                                    }
+                                }
+                            },
+                            if collapsed.read().contains(&collection.id) { "▸" } else { "▾" }
+                        }
+                    }
+                    span { class: "collection-name", "📁 {collection.name}" }
+                    span { class: "collection-count", "{counts.get(&collection.id).copied().unwrap_or(0)}" }
+                    button {
+                        class: "collection-action",
+                        title: "New collection inside",
+                        onclick: {
+                            let id = collection.id.clone();
+                            move |e: MouseEvent| {
+                                e.stop_propagation();
+                                on_add_child.call(id.clone());
+                            }
+                        },
+                        "+"
+                    }
+                    button {
+                        class: "collection-action",
+                        title: "Delete collection (its snippets move up a level)",
+                        onclick: {
+                            let id = collection.id.clone();
+                            move |e: MouseEvent| {
+                                e.stop_propagation();
+                                on_delete.call(id.clone());
+                            }
+                        },
+                        "×"
+                    }
+                }
+                if !collapsed.read().contains(&collection.id) {
+                    CollectionTree {
+                        collections: collections.clone(),
+                        parent: Some(collection.id.clone()),
+                        depth: depth + 1,
+                        counts: counts.clone(),
+                        selected: selected.clone(),
+                        collapsed,
+                        on_select,
+                        on_add_child,
+                        on_delete,
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+/// A modal asking the user to confirm a destructive action.
+#[component]
+pub fn ConfirmDialog(
+    title: String,
+    message: String,
+    confirm_label: String,
+    on_confirm: EventHandler<()>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div {
+            class: "dialog-backdrop",
+            onclick: move |_| on_cancel.call(()),
+            div {
+                class: "dialog",
+                role: "alertdialog",
+                onclick: move |e| e.stop_propagation(),
+                h3 { class: "dialog-title", "{title}" }
+                p { class: "dialog-message", "{message}" }
+                div { class: "dialog-actions",
+                    button {
+                        class: "btn btn-secondary",
+                        onclick: move |_| on_cancel.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "btn btn-danger",
+                        onclick: move |_| on_confirm.call(()),
+                        "{confirm_label}"
+                    }
+                }
+            }
+        }
+    }
+}
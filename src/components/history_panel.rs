@@ -0,0 +1,124 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use crate::diff::{diff_lines, LineChange};
+use crate::history::Revision;
+use crate::time;
+use super::relative_time::RelativeTime;
+
+/// Lists a snippet's revisions, diffs any two of them and restores old ones.
+#[component]
+pub fn HistoryPanel(history: Vec<Revision>, on_restore: EventHandler<usize>) -> Element {
+    // `None` follows the latest revisions as new ones are saved.
+    let mut compare_from = use_signal(|| None::<usize>);
+    let mut compare_to = use_signal(|| None::<usize>);
+
+    if history.len() < 2 {
+        return rsx! {
+            div { class: "history-panel",
+                p { class: "history-empty", "No earlier revisions yet. Every save from now on is kept here." }
+            }
+        };
+    }
+
+    let latest = history.len() - 1;
+    let to = compare_to().unwrap_or(latest).min(latest);
+    let from = compare_from().unwrap_or(latest - 1).min(latest);
+    let (old, new) = (&history[from], &history[to]);
+    let code_diff = diff_lines(&old.code, &new.code);
+
+    rsx! {
+        div { class: "history-panel",
+            div { class: "history-list",
+                for (index, revision) in history.iter().enumerate().rev() {
+                    div {
+                        key: "{index}",
+                        class: if index == from || index == to { "history-item selected" } else { "history-item" },
+                        span { class: "history-version", "v{index + 1}" }
+                        span { class: "history-title", "{revision.title}" }
+                        span { class: "date", RelativeTime { at: revision.saved_at } }
+                        div { class: "history-actions",
+                            button {
+                                class: "btn btn-ghost btn-sm",
+                                disabled: index == latest,
+                                onclick: move |_| {
+                                    compare_from.set(Some(index));
+                                    compare_to.set(None);
+                                },
+                                "Compare"
+                            }
+                            if index == latest {
+                                span { class: "history-current", "Current" }
+                            } else {
+                                button {
+                                    class: "btn btn-secondary btn-sm",
+                                    onclick: move |_| on_restore.call(index),
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "history-compare",
+                label { class: "form-label", "From" }
+                select {
+                    class: "form-select",
+                    onchange: move |e| compare_from.set(e.value().parse().ok()),
+                    for (index, revision) in history.iter().enumerate() {
+                        option {
+                            value: "{index}",
+                            selected: index == from,
+                            "v{index + 1} · {time::absolute(revision.saved_at)}"
+                        }
+                    }
+                }
+                label { class: "form-label", "To" }
+                select {
+                    class: "form-select",
+                    onchange: move |e| compare_to.set(e.value().parse().ok()),
+                    for (index, revision) in history.iter().enumerate() {
+                        option {
+                            value: "{index}",
+                            selected: index == to,
+                            "v{index + 1} · {time::absolute(revision.saved_at)}"
+                        }
+                    }
+                }
+            }
+
+            div { class: "diff-view",
+                if old.title != new.title {
+                    div { class: "diff-meta", "Title: “{old.title}” → “{new.title}”" }
+                }
+                if old.language != new.language {
+                    div { class: "diff-meta", "Language: {old.language} → {new.language}" }
+                }
+                if old.description != new.description {
+                    div { class: "diff-meta", "Description changed" }
+                }
+                if old.tags != new.tags {
+                    div { class: "diff-meta", "Tags: {old.tags.join(\", \")} → {new.tags.join(\", \")}" }
+                }
+                pre { class: "diff-code",
+                    for line in code_diff {
+                        div {
+                            class: match line.change {
+                                LineChange::Unchanged => "diff-line",
+                                LineChange::Added => "diff-line added",
+                                LineChange::Removed => "diff-line removed",
+                            },
+                            match line.change {
+                                LineChange::Unchanged => "  ",
+                                LineChange::Added => "+ ",
+                                LineChange::Removed => "- ",
+                            }
+                            "{line.text}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,11 +1,19 @@
 #![allow(non_snake_case)]
+use std::collections::HashMap;
+
 use dioxus::prelude::*;
-use crate::Snippet;
+use crate::collections::{self, Collection};
+use crate::search::{self, query};
+use crate::search::query::Term;
+use crate::settings::{SavedSearch, SortOrder};
+use crate::{tags, AppState, Snippet};
+use super::collection_sidebar::CollectionSidebar;
+use super::confirm_dialog::ConfirmDialog;
 use super::snippet_card::SnippetCard;
+use super::tag_tree::TagTree;
 
 #[component]
 pub fn HomeScreen(
-    snippets: Vec<Snippet>,
     search_query: String,
     selected_language: String,
     on_search: EventHandler<String>,
@@ -14,9 +22,70 @@ pub fn HomeScreen(
     on_edit_snippet: EventHandler<String>,
     on_delete_snippet: EventHandler<String>,
     on_toggle_favorite: EventHandler<String>,
+    on_copy_snippet: EventHandler<String>,
+    saved_searches: Vec<SavedSearch>,
+    on_save_search: EventHandler<SavedSearch>,
+    on_remove_saved_search: EventHandler<String>,
+    collections: Vec<Collection>,
+    selected_collection: Option<String>,
+    on_select_collection: EventHandler<Option<String>>,
+    on_create_collection: EventHandler<(String, Option<String>)>,
+    on_delete_collection: EventHandler<String>,
+    /// A snippet id and the collection to file it in.
+    on_move_snippet: EventHandler<(String, Option<String>)>,
+    sort_order: SortOrder,
+    on_sort: EventHandler<SortOrder>,
 ) -> Element {
+    // The snippet whose delete is waiting for confirmation, as (id, title).
+    let mut pending_delete = use_signal(|| None::<(String, String)>);
+    // Treats the whole search box as one regular expression over the code.
+    let mut regex_mode = use_signal(|| false);
+    // The name being typed for the current search, while saving it.
+    let mut naming_search = use_signal(|| None::<String>);
+    let mut show_collections = use_signal(|| false);
+    let mut show_tags = use_signal(|| false);
+
+    // Borrowed straight from the store rather than passed in, so a render
+    // doesn't copy the vault; only the cards shown get their own copy.
+    let store = use_context::<AppState>().store;
+    let vault = store.read();
+    let all = vault.list();
+
+    let live_total = all.iter().filter(|s| !s.is_trashed()).count();
+    let collection_counts: HashMap<String, usize> = {
+        let mut direct: HashMap<&str, usize> = HashMap::new();
+        for snippet in all.iter().filter(|s| !s.is_trashed()) {
+            if let Some(id) = &snippet.collection {
+                *direct.entry(id.as_str()).or_default() += 1;
+            }
+        }
+        collections.iter()
+            .map(|collection| {
+                let total = collections::subtree(&collections, &collection.id)
+                    .iter()
+                    .map(|id| direct.get(id.as_str()).copied().unwrap_or(0))
+                    .sum();
+                (collection.id.clone(), total)
+            })
+            .collect()
+    };
+
+    // Everything below, search included, only sees the selected collection
+    // and the ones nested in it.
+    let scope_path = selected_collection.as_deref().map(|id| collections::path(&collections, id));
+    let snippets: Vec<&Snippet> = match selected_collection.as_deref() {
+        Some(id) => {
+            let scope = collections::subtree(&collections, id);
+            all.iter()
+                .filter(|snippet| snippet.collection.as_ref().is_some_and(|c| scope.contains(c)))
+                .collect()
+        }
+        None => all.iter().collect(),
+    };
+
     let languages: Vec<String> = {
-        let mut langs: std::collections::HashSet<String> = snippets.iter()
+        let langs: std::collections::HashSet<String> = snippets.iter()
+            .filter(|s| !s.is_trashed())
             .map(|s| s.language.clone())
             .collect();
         let mut lang_vec: Vec<String> = langs.into_iter().collect();
@@ -24,36 +93,170 @@ pub fn HomeScreen(
         lang_vec
     };
 
-    let filtered_snippets: Vec<Snippet> = snippets.into_iter()
-        .filter(|snippet| {
-            let matches_search = search_query.is_empty() || 
-                snippet.title.to_lowercase().contains(&search_query.to_lowercase()) ||
-                snippet.code.to_lowercase().contains(&search_query.to_lowercase()) ||
-                snippet.tags.iter().any(|tag| tag.to_lowercase().contains(&search_query.to_lowercase()));
-            
-            let matches_language = selected_language.is_empty() || snippet.language == selected_language;
-            
-            matches_search && matches_language
+    // While a query is malformed (often just half-typed) the error is shown
+    // and the raw text is searched for as-is.
+    let parsed_query = if regex_mode() {
+        query::parse_regex(&search_query)
+    } else {
+        query::parse(&search_query)
+    };
+    let query_error = parsed_query.as_ref().err().map(ToString::to_string);
+
+    let tag_tree = tags::tree(snippets.iter().copied());
+    let active_tag = parsed_query.as_ref().ok().and_then(|query| {
+        query.clauses.iter().find_map(|clause| match &clause.term {
+            Term::Tag(tag) if !clause.negated => Some(tag.clone()),
+            _ => None,
         })
+    });
+
+    let mut filtered_snippets: Vec<&Snippet> = match &parsed_query {
+        Ok(query) => query.clone()
+            .in_language(&selected_language)
+            .rank(snippets.iter().copied(), vault.search_index()),
+        Err(_) if regex_mode() => Vec::new(),
+        Err(_) => snippets.iter()
+            .filter(|snippet| !snippet.is_trashed())
+            .filter(|snippet| selected_language.is_empty() || snippet.language == selected_language)
+            .filter(|snippet| search::matches(snippet, search_query.trim()))
+            .copied()
+            .collect(),
+    };
+
+    sort_order.sort(&mut filtered_snippets);
+
+    // Counted afresh on every render, so the chips follow edits to the vault.
+    let saved_counts: Vec<usize> = saved_searches.iter()
+        .map(|saved| saved.parse().map_or(0, |query| query.rank(snippets.iter().copied(), vault.search_index()).len()))
         .collect();
+    let active_saved = saved_searches.iter().position(|saved| {
+        saved.query == search_query && saved.language == selected_language && saved.regex == regex_mode()
+    });
+    let can_save = active_saved.is_none()
+        && query_error.is_none()
+        && !(search_query.trim().is_empty() && selected_language.is_empty());
 
     rsx! {
         div { class: "home-screen",
+            if show_collections() {
+                CollectionSidebar {
+                    collections: collections.clone(),
+                    counts: collection_counts,
+                    total: live_total,
+                    selected: selected_collection.clone(),
+                    on_select: on_select_collection,
+                    on_create: on_create_collection,
+                    on_delete: on_delete_collection,
+                }
+            }
+
+            if show_tags() {
+                TagTree {
+                    nodes: tag_tree,
+                    active: active_tag,
+                    on_select: move |path: String| {
+                        regex_mode.set(false);
+                        if path.contains(char::is_whitespace) {
+                            on_search.call(format!("tag:\"{path}\""));
+                        } else {
+                            on_search.call(format!("tag:{path}"));
+                        }
+                    },
+                }
+            }
+
             // Search and Filter Section
             div { class: "search-section",
+                div { class: "collection-bar",
+                    button {
+                        class: if show_collections() { "btn btn-secondary btn-sm" } else { "btn btn-ghost btn-sm" },
+                        onclick: move |_| show_collections.toggle(),
+                        span { class: "btn-icon", "📁" }
+                        "Collections"
+                    }
+                    button {
+                        class: if show_tags() { "btn btn-secondary btn-sm" } else { "btn btn-ghost btn-sm" },
+                        onclick: move |_| show_tags.toggle(),
+                        span { class: "btn-icon", "🏷️" }
+                        "Tags"
+                    }
+                    select {
+                        class: "form-select sort-select",
+                        title: "Sort by",
+                        onchange: move |e| {
+                            if let Some(order) = SortOrder::from_key(&e.value()) {
+                                on_sort.call(order);
+                            }
+                        },
+                        for order in SortOrder::ALL {
+                            option {
+                                value: order.key(),
+                                selected: order == sort_order,
+                                "{order.label()}"
+                            }
+                        }
+                    }
+                    if let Some(path) = scope_path {
+                        span { class: "collection-scope",
+                            "In {path}"
+                            button {
+                                class: "chip-remove",
+                                title: "Show all snippets",
+                                onclick: move |_| on_select_collection.call(None),
+                                "×"
+                            }
+                        }
+                    }
+                }
+
                 div { class: "search-bar",
                     input {
                         class: "search-input",
                         r#type: "text",
-                        placeholder: "Search snippets...",
+                        placeholder: "Search snippets... (try lang:rust tag:async -deprecated)",
+                        title: "Filters: lang:, tag:, fav:true, created:/updated: with >, >=, <, <= and a date, \"exact phrase\", and - to exclude",
                         value: "{search_query}",
                         oninput: move |e| on_search.call(e.value())
                     }
                     span { class: "search-icon", "🔍" }
+                    button {
+                        class: if regex_mode() { "regex-toggle active" } else { "regex-toggle" },
+                        title: "Regex mode: search code line by line with a regular expression",
+                        onclick: move |_| regex_mode.toggle(),
+                        ".*"
+                    }
+                }
+
+                if let Some(err) = query_error {
+                    p { class: "query-error", "{err}" }
                 }
                 
-                if !languages.is_empty() {
+                if !languages.is_empty() || !saved_searches.is_empty() {
                     div { class: "filter-chips",
+                        for (index, (saved, count)) in saved_searches.into_iter().zip(saved_counts).enumerate() {
+                            if active_saved == Some(index) {
+                                span { class: "saved-chip",
+                                    button { class: "chip saved active", "★ {saved.name} ({count})" }
+                                    button {
+                                        class: "chip-remove",
+                                        title: "Remove saved search",
+                                        onclick: move |_| on_remove_saved_search.call(saved.name.clone()),
+                                        "×"
+                                    }
+                                }
+                            } else {
+                                button {
+                                    class: "chip saved",
+                                    title: "{saved.query}",
+                                    onclick: move |_| {
+                                        regex_mode.set(saved.regex);
+                                        on_search.call(saved.query.clone());
+                                        on_language_filter.call(saved.language.clone());
+                                    },
+                                    "★ {saved.name} ({count})"
+                                }
+                            }
+                        }
                         button {
                             class: if selected_language.is_empty() { "chip active" } else { "chip" },
                             onclick: move |_| on_language_filter.call(String::new()),
@@ -69,6 +272,55 @@ pub fn HomeScreen(
                                 "{language}"
                             }
                         }
+                        if can_save && naming_search().is_none() {
+                            button {
+                                class: "chip chip-add",
+                                onclick: {
+                                    let name = if search_query.trim().is_empty() { selected_language.clone() } else { search_query.trim().to_string() };
+                                    move |_| naming_search.set(Some(name.clone()))
+                                },
+                                "☆ Save search"
+                            }
+                        }
+                    }
+                }
+
+                if let Some(name) = naming_search() {
+                    form {
+                        class: "save-search-form",
+                        onsubmit: {
+                            let query = search_query.clone();
+                            let language = selected_language.clone();
+                            move |e: FormEvent| {
+                                e.prevent_default();
+                                let Some(name) = naming_search.take().filter(|n| !n.trim().is_empty()) else {
+                                    return;
+                                };
+                                on_save_search.call(SavedSearch {
+                                    name: name.trim().to_string(),
+                                    query: query.clone(),
+                                    language: language.clone(),
+                                    regex: regex_mode(),
+                                });
+                            }
+                        },
+                        input {
+                            class: "form-input",
+                            r#type: "text",
+                            placeholder: "Name this search",
+                            value: "{name}",
+                            onmounted: move |e| async move {
+                                let _ = e.set_focus(true).await;
+                            },
+                            oninput: move |e| naming_search.set(Some(e.value())),
+                        }
+                        button { class: "btn btn-primary btn-sm", r#type: "submit", "Save" }
+                        button {
+                            class: "btn btn-ghost btn-sm",
+                            r#type: "button",
+                            onclick: move |_| naming_search.set(None),
+                            "Cancel"
+                        }
                     }
                 }
             }
@@ -101,17 +353,46 @@ pub fn HomeScreen(
                                 move |_| on_edit_snippet.call(id.clone())
                             },
                             on_delete: {
-                                let id = snippet.id.clone();
-                                move |_| on_delete_snippet.call(id.clone())
+                                let pending = (snippet.id.clone(), snippet.title.clone());
+                                move |_| pending_delete.set(Some(pending.clone()))
                             },
                             on_toggle_favorite: {
                                 let id = snippet.id.clone();
                                 move |_| on_toggle_favorite.call(id.clone())
-                            }
+                            },
+                            on_copy: {
+                                let id = snippet.id.clone();
+                                move |_| on_copy_snippet.call(id.clone())
+                            },
+                            collections: collections.clone(),
+                            on_move: {
+                                let id = snippet.id.clone();
+                                move |collection: Option<String>| on_move_snippet.call((id.clone(), collection))
+                            },
+                            matching_lines: match &parsed_query {
+                                Ok(query) => query.matching_lines(&snippet.code)
+                                    .into_iter()
+                                    .map(|(number, line)| (number, line.to_string()))
+                                    .collect(),
+                                Err(_) => Vec::new(),
+                            },
                         }
                     }
                 }
             }
+
+            if let Some((id, title)) = pending_delete() {
+                ConfirmDialog {
+                    title: "Delete snippet?",
+                    message: "\"{title}\" will be moved to the trash.",
+                    confirm_label: "Delete",
+                    on_confirm: move |_| {
+                        pending_delete.set(None);
+                        on_delete_snippet.call(id.clone());
+                    },
+                    on_cancel: move |_| pending_delete.set(None),
+                }
+            }
         }
     }
 }
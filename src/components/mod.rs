@@ -2,4 +2,16 @@ pub mod home_screen;
 pub mod add_edit_snippet;
 pub mod snippet_detail;
 pub mod snippet_card;
+pub mod tag_editor;
+pub mod tag_manager;
+pub mod tag_tree;
 pub mod ai_explain;
+pub mod code_block;
+pub mod collection_sidebar;
+pub mod confirm_dialog;
+pub mod history_panel;
+pub mod relative_time;
+pub mod search_screen;
+pub mod settings_screen;
+pub mod toast;
+pub mod trash_screen;
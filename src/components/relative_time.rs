@@ -0,0 +1,17 @@
+#![allow(non_snake_case)]
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+
+use crate::time;
+
+/// A relative time ("3 days ago") with the absolute time on hover.
+#[component]
+pub fn RelativeTime(at: DateTime<Utc>) -> Element {
+    rsx! {
+        time {
+            datetime: "{at.to_rfc3339()}",
+            title: "{time::absolute(at)}",
+            "{time::relative(at, time::now())}"
+        }
+    }
+}
@@ -0,0 +1,179 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use crate::search;
+use crate::{AppState, Snippet};
+
+/// Keeps the keyboard-selected result visible while arrowing through a long list.
+const SCROLL_TO_ACTIVE: &str =
+    "document.querySelector('.search-result.active')?.scrollIntoView({ block: 'nearest' });";
+
+#[component]
+pub fn SearchScreen(
+    query: String,
+    recent_searches: Vec<String>,
+    on_query: EventHandler<String>,
+    on_open: EventHandler<String>,
+    on_clear_recent: EventHandler<()>,
+) -> Element {
+    let mut active = use_signal(|| 0usize);
+
+    use_effect(move || {
+        active();
+        document::eval(SCROLL_TO_ACTIVE);
+    });
+
+    // Borrowed straight from the store rather than passed in, so each
+    // keystroke doesn't copy the vault.
+    let store = use_context::<AppState>().store;
+    let vault = store.read();
+
+    let needle = query.trim().to_string();
+    let results: Vec<&Snippet> = if needle.is_empty() {
+        Vec::new()
+    } else {
+        vault.list().iter()
+            .filter(|snippet| !snippet.is_trashed() && search::matches(snippet, &needle))
+            .collect()
+    };
+    let active_index = active().min(results.len().saturating_sub(1));
+    let result_ids: Vec<String> = results.iter().map(|snippet| snippet.id.clone()).collect();
+
+    rsx! {
+        div { class: "search-screen",
+            div { class: "search-bar",
+                input {
+                    class: "search-input",
+                    r#type: "search",
+                    placeholder: "Search titles, descriptions, code and tags...",
+                    value: "{query}",
+                    onmounted: move |e| async move {
+                        let _ = e.set_focus(true).await;
+                    },
+                    oninput: move |e| {
+                        active.set(0);
+                        on_query.call(e.value());
+                    },
+                    onkeydown: move |e| match e.key() {
+                        Key::ArrowDown => {
+                            e.prevent_default();
+                            if active_index + 1 < result_ids.len() {
+                                active.set(active_index + 1);
+                            }
+                        }
+                        Key::ArrowUp => {
+                            e.prevent_default();
+                            active.set(active_index.saturating_sub(1));
+                        }
+                        Key::Enter => {
+                            if let Some(id) = result_ids.get(active_index) {
+                                on_open.call(id.clone());
+                            }
+                        }
+                        Key::Escape => on_query.call(String::new()),
+                        _ => {}
+                    },
+                }
+                span { class: "search-icon", "🔍" }
+            }
+
+            if needle.is_empty() {
+                if recent_searches.is_empty() {
+                    div { class: "empty-state",
+                        div { class: "empty-icon", "🔍" }
+                        h3 { "Search your vault" }
+                        p { "Press / from anywhere to jump here." }
+                    }
+                } else {
+                    div { class: "recent-searches",
+                        div { class: "recent-searches-header",
+                            h3 { class: "form-section-title", "Recent searches" }
+                            button {
+                                class: "btn btn-ghost btn-sm",
+                                onclick: move |_| on_clear_recent.call(()),
+                                "Clear"
+                            }
+                        }
+                        div { class: "filter-chips",
+                            for recent in recent_searches {
+                                button {
+                                    class: "chip",
+                                    onclick: {
+                                        let recent = recent.clone();
+                                        move |_| on_query.call(recent.clone())
+                                    },
+                                    "{recent}"
+                                }
+                            }
+                        }
+                    }
+                }
+            } else if results.is_empty() {
+                div { class: "empty-state",
+                    div { class: "empty-icon", "📝" }
+                    h3 { "No snippets found" }
+                    p { "Nothing matches “{needle}”." }
+                }
+            } else {
+                p { class: "search-count",
+                    if results.len() == 1 { "1 result" } else { "{results.len()} results" }
+                }
+                div { class: "search-results",
+                    for (index, snippet) in results.into_iter().enumerate() {
+                        div {
+                            key: "{snippet.id}",
+                            class: if index == active_index { "search-result active" } else { "search-result" },
+                            onclick: {
+                                let id = snippet.id.clone();
+                                move |_| on_open.call(id.clone())
+                            },
+                            onmouseenter: move |_| active.set(index),
+
+                            div { class: "card-title-section",
+                                h3 { class: "card-title",
+                                    Highlighted { text: snippet.title.clone(), query: needle.clone() }
+                                }
+                                span { class: "language-badge {snippet.language}", "{snippet.language}" }
+                            }
+                            if let Some(description) = snippet.description.clone().filter(|d| search::find(d, &needle).is_some()) {
+                                p { class: "card-description",
+                                    Highlighted { text: description, query: needle.clone() }
+                                }
+                            }
+                            if let Some((number, line)) = search::matching_line(&snippet.code, &needle) {
+                                div { class: "search-result-line",
+                                    span { class: "line-number", "{number}" }
+                                    code { Highlighted { text: line.to_string(), query: needle.clone() } }
+                                }
+                            }
+                            if !snippet.tags.is_empty() {
+                                div { class: "tags-container",
+                                    for tag in snippet.tags.iter() {
+                                        span { class: "tag",
+                                            "#"
+                                            Highlighted { text: tag.clone(), query: needle.clone() }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `text` with every occurrence of `query` marked.
+#[component]
+fn Highlighted(text: String, query: String) -> Element {
+    rsx! {
+        for (part, hit) in search::segments(&text, &query) {
+            if hit {
+                mark { class: "search-hit", "{part}" }
+            } else {
+                "{part}"
+            }
+        }
+    }
+}
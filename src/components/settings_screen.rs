@@ -0,0 +1,165 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use crate::ai::ExplainConfig;
+use crate::settings::{CodeTheme, Settings, TagRules, MAX_TRASH_RETENTION_DAYS};
+use crate::tags::LEVEL_SEPARATOR;
+
+#[component]
+pub fn SettingsScreen(settings: Settings, on_save: EventHandler<Settings>) -> Element {
+    let mut base_url = use_signal(|| settings.explain.base_url.clone());
+    let mut model = use_signal(|| settings.explain.model.clone());
+    let mut api_key = use_signal(|| settings.explain.api_key.clone().unwrap_or_default());
+    let mut code_theme = use_signal(|| settings.code_theme);
+    let mut retention_days = use_signal(|| settings.trash_retention_days.to_string());
+    let mut tag_separators = use_signal(|| settings.tag_rules.separators.clone());
+    let mut lowercase_tags = use_signal(|| settings.tag_rules.lowercase);
+    let mut tag_space_replacement = use_signal(|| settings.tag_rules.space_replacement.clone());
+
+    rsx! {
+        div { class: "add-edit-screen",
+            div { class: "form-container",
+                h2 { class: "form-title", "Settings" }
+
+                form {
+                    class: "snippet-form",
+                    onsubmit: move |e| {
+                        e.prevent_default();
+                        let mut updated = settings.clone();
+                        updated.explain = ExplainConfig {
+                            base_url: base_url().trim().to_string(),
+                            model: model().trim().to_string(),
+                            api_key: if api_key().is_empty() { None } else { Some(api_key()) },
+                        };
+                        updated.code_theme = code_theme();
+                        updated.trash_retention_days = retention_days()
+                            .trim()
+                            .parse::<u32>()
+                            .map_or(settings.trash_retention_days, |days| days.min(MAX_TRASH_RETENTION_DAYS));
+                        // `/` nests tags, so it can't also end or join one.
+                        updated.tag_rules = TagRules {
+                            separators: tag_separators().replace(LEVEL_SEPARATOR, ""),
+                            lowercase: lowercase_tags(),
+                            space_replacement: tag_space_replacement().replace(LEVEL_SEPARATOR, ""),
+                        };
+                        on_save.call(updated);
+                    },
+
+                    h3 { class: "form-section-title", "Appearance" }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "Code Theme" }
+                        div { class: "filter-chips",
+                            for theme in CodeTheme::ALL {
+                                button {
+                                    class: if code_theme() == theme { "chip active" } else { "chip" },
+                                    r#type: "button",
+                                    onclick: move |_| code_theme.set(theme),
+                                    "{theme.label()}"
+                                }
+                            }
+                        }
+                    }
+
+                    h3 { class: "form-section-title", "Tags" }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "Separators" }
+                        input {
+                            class: "form-input",
+                            r#type: "text",
+                            placeholder: ",",
+                            value: "{tag_separators}",
+                            oninput: move |e| tag_separators.set(e.value())
+                        }
+                        p { class: "form-hint", "Typing any of these characters finishes a tag, as Enter does. \"{LEVEL_SEPARATOR}\" always nests tags instead." }
+                    }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "Spaces inside a tag become" }
+                        input {
+                            class: "form-input",
+                            r#type: "text",
+                            placeholder: "-",
+                            value: "{tag_space_replacement}",
+                            oninput: move |e| tag_space_replacement.set(e.value())
+                        }
+                        p { class: "form-hint", "Leave empty to keep a single space." }
+                    }
+
+                    div { class: "form-group",
+                        label { class: "form-checkbox",
+                            input {
+                                r#type: "checkbox",
+                                checked: lowercase_tags(),
+                                onchange: move |e| lowercase_tags.set(e.checked())
+                            }
+                            "Lowercase tags"
+                        }
+                    }
+
+                    h3 { class: "form-section-title", "Trash" }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "Keep trashed snippets for (days)" }
+                        input {
+                            class: "form-input",
+                            r#type: "number",
+                            min: "0",
+                            max: "{MAX_TRASH_RETENTION_DAYS}",
+                            value: "{retention_days}",
+                            oninput: move |e| retention_days.set(e.value())
+                        }
+                        p { class: "form-hint", "Use 0 to keep them until the trash is emptied by hand." }
+                    }
+
+                    h3 { class: "form-section-title", "AI Explain" }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "Provider URL" }
+                        input {
+                            class: "form-input",
+                            r#type: "url",
+                            placeholder: "http://localhost:11434/v1",
+                            value: "{base_url}",
+                            oninput: move |e| base_url.set(e.value())
+                        }
+                        p { class: "form-hint",
+                            "Any OpenAI-compatible API: OpenAI, Ollama, llama.cpp or a mock server. Leave empty to disable."
+                        }
+                    }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "Model" }
+                        input {
+                            class: "form-input",
+                            r#type: "text",
+                            placeholder: "llama3.2",
+                            value: "{model}",
+                            oninput: move |e| model.set(e.value())
+                        }
+                    }
+
+                    div { class: "form-group",
+                        label { class: "form-label", "API Key (Optional)" }
+                        input {
+                            class: "form-input",
+                            r#type: "password",
+                            value: "{api_key}",
+                            oninput: move |e| api_key.set(e.value())
+                        }
+                    }
+
+                    div { class: "form-actions",
+                        button {
+                            class: "btn btn-primary",
+                            r#type: "submit",
+                            span { class: "btn-icon", "💾" }
+                            "Save Settings"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,17 @@
 #![allow(non_snake_case)]
 use dioxus::prelude::*;
+use crate::collections::{self, Collection};
+use crate::preview::code_preview;
+use crate::tags;
 use crate::Snippet;
+use super::code_block::CodeBlock;
+use super::relative_time::RelativeTime;
+
+/// How much code a card shows before "+N more lines".
+const PREVIEW_LINES: usize = 6;
+const PREVIEW_CHARS: usize = 300;
+/// How many regex-matched lines a card lists before "+N more".
+const MATCHING_LINES: usize = 5;
 
 #[component]
 pub fn SnippetCard(
@@ -9,12 +20,24 @@ pub fn SnippetCard(
     on_edit: EventHandler<()>,
     on_delete: EventHandler<()>,
     on_toggle_favorite: EventHandler<()>,
+    on_copy: EventHandler<()>,
+    /// Lines picked out by a regex search, as (line number, text).
+    #[props(default)]
+    matching_lines: Vec<(usize, String)>,
+    /// Every collection, for the "move to" menu.
+    collections: Vec<Collection>,
+    on_move: EventHandler<Option<String>>,
 ) -> Element {
-    let code_preview = if snippet.code.len() > 100 {
-        format!("{}...", &snippet.code[..100])
-    } else {
-        snippet.code.clone()
-    };
+    let mut move_menu_open = use_signal(|| false);
+    let mut destinations: Vec<(String, String)> = collections.iter()
+        .map(|collection| (collection.id.clone(), collections::path(&collections, &collection.id)))
+        .collect();
+    destinations.sort_by_key(|(_, path)| path.to_lowercase());
+    let current_path = snippet.collection.as_deref()
+        .map(|id| collections::path(&collections, id))
+        .filter(|path| !path.is_empty());
+    let preview = code_preview(&snippet.code, PREVIEW_LINES, PREVIEW_CHARS);
+    let hidden_matches = matching_lines.len().saturating_sub(MATCHING_LINES);
 
     rsx! {
         div { 
@@ -36,6 +59,24 @@ pub fn SnippetCard(
                         },
                         "★"
                     }
+                    button {
+                        class: "action-btn",
+                        title: "Copy code",
+                        onclick: move |e| {
+                            e.stop_propagation();
+                            on_copy.call(());
+                        },
+                        "📋"
+                    }
+                    button {
+                        class: "action-btn",
+                        title: "Move to collection",
+                        onclick: move |e| {
+                            e.stop_propagation();
+                            move_menu_open.toggle();
+                        },
+                        "📁"
+                    }
                     button {
                         class: "action-btn",
                         onclick: move |e| {
@@ -55,26 +96,77 @@ pub fn SnippetCard(
                 }
             }
 
+            if move_menu_open() {
+                div {
+                    class: "move-menu",
+                    onclick: move |e| e.stop_propagation(),
+                    div { class: "move-menu-title", "Move to" }
+                    button {
+                        class: if snippet.collection.is_none() { "move-menu-item active" } else { "move-menu-item" },
+                        onclick: move |_| {
+                            move_menu_open.set(false);
+                            on_move.call(None);
+                        },
+                        "No collection"
+                    }
+                    for (id, path) in destinations {
+                        button {
+                            class: if snippet.collection.as_deref() == Some(id.as_str()) { "move-menu-item active" } else { "move-menu-item" },
+                            onclick: {
+                                let id = id.clone();
+                                move |_| {
+                                    move_menu_open.set(false);
+                                    on_move.call(Some(id.clone()));
+                                }
+                            },
+                            "📁 {path}"
+                        }
+                    }
+                }
+            }
+
             if let Some(description) = &snippet.description {
                 p { class: "card-description", "{description}" }
             }
 
-            div { class: "code-preview",
-                pre { class: "code-block {snippet.language}",
-                    code { "{code_preview}" }
+            if matching_lines.is_empty() {
+                div { class: "code-preview",
+                    CodeBlock { code: preview.text, language: snippet.language.clone() }
+                    if preview.hidden_lines > 0 {
+                        div { class: "more-lines",
+                            if preview.hidden_lines == 1 { "+1 more line" } else { "+{preview.hidden_lines} more lines" }
+                        }
+                    }
+                }
+            } else {
+                div { class: "matching-lines",
+                    for (number, line) in matching_lines.iter().take(MATCHING_LINES) {
+                        div { class: "matching-line",
+                            span { class: "line-number", "{number}" }
+                            code { "{line}" }
+                        }
+                    }
+                    if hidden_matches > 0 {
+                        div { class: "more-lines",
+                            if hidden_matches == 1 { "+1 more matching line" } else { "+{hidden_matches} more matching lines" }
+                        }
+                    }
                 }
             }
 
             if !snippet.tags.is_empty() {
                 div { class: "tags-container",
                     for tag in &snippet.tags {
-                        span { class: "tag", "#{tag}" }
+                        span { class: "tag", title: "#{tag}", "#{tags::leaf(tag)}" }
                     }
                 }
             }
 
             div { class: "card-footer",
-                span { class: "date", "Updated {snippet.updated_at}" }
+                span { class: "date", "Updated " RelativeTime { at: snippet.updated_at } }
+                if let Some(path) = current_path {
+                    span { class: "card-collection", "📁 {path}" }
+                }
             }
         }
     }
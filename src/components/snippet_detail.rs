@@ -1,7 +1,13 @@
 #![allow(non_snake_case)]
 
 use dioxus::prelude::*;
-use crate::Snippet;
+use crate::ai::{self, CachedExplanation, ExplainRequest};
+use crate::{time, AppState, Snippet};
+use super::ai_explain::AIExplain;
+use super::code_block::CodeBlock;
+use super::confirm_dialog::ConfirmDialog;
+use super::history_panel::HistoryPanel;
+use super::relative_time::RelativeTime;
 
 #[component]
 pub fn SnippetDetail(
@@ -9,9 +15,15 @@ pub fn SnippetDetail(
     on_edit: EventHandler<String>,
     on_delete: EventHandler<String>,
     on_toggle_favorite: EventHandler<String>,
+    on_copy: EventHandler<String>,
+    on_explained: EventHandler<CachedExplanation>,
+    on_restore_revision: EventHandler<(String, usize)>,
 ) -> Element {
-    let mut ai_explanation = use_signal(|| None::<String>);
-    let mut ai_loading = use_signal(|| false);
+    let settings = use_context::<AppState>().settings;
+    let mut ai_error = use_signal(|| None::<String>);
+    let mut ai_task = use_signal(|| None::<Task>);
+    let mut confirming_delete = use_signal(|| false);
+    let mut show_history = use_signal(|| false);
 
     if let Some(snippet) = snippet {
         // Clone the ID once outside the closures to avoid multiple moves
@@ -19,6 +31,11 @@ pub fn SnippetDetail(
         let snippet_id_for_edit = snippet_id.clone();
         let snippet_id_for_delete = snippet_id.clone();
         let snippet_id_for_favorite = snippet_id.clone();
+        let snippet_id_for_copy = snippet_id.clone();
+        let explanation_stale = snippet
+            .explanation
+            .as_ref()
+            .is_some_and(|cached| cached.is_stale(&snippet.language, &snippet.code));
         
         rsx! {
             div { class: "snippet-detail",
@@ -53,53 +70,79 @@ pub fn SnippetDetail(
                         div { class: "code-title", "Code" }
                         button {
                             class: "btn btn-sm btn-ghost",
-                            onclick: move |_| {
-                                // Simple clipboard copy - using console log for now
-                                web_sys::console::log_1(&"Copy to clipboard clicked".into());
-                                // TODO: implement proper clipboard copy when needed
-                            },
+                            onclick: move |_| on_copy.call(snippet_id_for_copy.clone()),
                             "📋 Copy"
                         }
                     }
                     div { class: "code-container",
-                        pre { class: "code-block {snippet.language}",
-                            code { "{snippet.code}" }
-                        }
+                        CodeBlock { code: snippet.code.clone(), language: snippet.language.clone() }
                     }
                 }
 
-                // AI Explain section
-                div { class: "ai-section",
+                // History section
+                div { class: "history-section",
                     button {
-                        class: "btn btn-ai",
-                        onclick: move |_| {
-                            ai_loading.set(true);
-                            let explanation = format!(
-                                "This {} code snippet demonstrates {}. It's commonly used for {} and showcases {} programming concepts.",
-                                snippet.language,
-                                "core programming functionality",
-                                "educational purposes and real-world applications",
-                                "essential"
-                            );
-                            ai_explanation.set(Some(explanation));
-                            ai_loading.set(false);
-                        },
-                        disabled: ai_loading(),
-                        span { class: "btn-icon", "🤖" }
-                        if ai_loading() { "Analyzing..." } else { "AI Explain" }
+                        class: "btn btn-sm btn-ghost",
+                        onclick: move |_| show_history.toggle(),
+                        span { class: "btn-icon", "🕘" }
+                        if show_history() { "Hide history" } else { "History ({snippet.history.len()})" }
                     }
-
-                    if let Some(expl) = ai_explanation() {
-                        div { class: "ai-explanation",
-                            div { class: "ai-header",
-                                span { class: "ai-icon", "🤖" }
-                                "AI Explanation"
-                            }
-                            p { class: "ai-text", "{expl}" }
+                    if show_history() {
+                        HistoryPanel {
+                            history: snippet.history.clone(),
+                            on_restore: {
+                                let id = snippet_id.clone();
+                                move |index: usize| on_restore_revision.call((id.clone(), index))
+                            },
                         }
                     }
                 }
 
+                // AI Explain section
+                AIExplain {
+                    explanation: snippet.explanation.as_ref().map(|cached| cached.text.clone()),
+                    stale: explanation_stale,
+                    loading: ai_task().is_some(),
+                    error: ai_error(),
+                    on_request: {
+                        let request = ExplainRequest {
+                            title: snippet.title.clone(),
+                            language: snippet.language.clone(),
+                            code: snippet.code.clone(),
+                        };
+                        move |_| {
+                            ai_error.set(None);
+                            let provider = match settings.read().explain.provider() {
+                                Ok(provider) => provider,
+                                Err(err) => {
+                                    ai_error.set(Some(err.to_string()));
+                                    return;
+                                }
+                            };
+                            let request = request.clone();
+                            // Owned by this component, so leaving the screen cancels the request too.
+                            let task = spawn(async move {
+                                let content_hash = ai::content_hash(&request.language, &request.code);
+                                match provider.explain(request).await {
+                                    Ok(text) => on_explained.call(CachedExplanation {
+                                        text,
+                                        content_hash,
+                                        generated_at: time::now(),
+                                    }),
+                                    Err(err) => ai_error.set(Some(err.to_string())),
+                                }
+                                ai_task.set(None);
+                            });
+                            ai_task.set(Some(task));
+                        }
+                    },
+                    on_cancel: move |_| {
+                        if let Some(task) = ai_task.take() {
+                            task.cancel();
+                        }
+                    },
+                }
+
                 // Action buttons
                 div { class: "detail-actions",
                     button {
@@ -110,11 +153,7 @@ pub fn SnippetDetail(
                     }
                     button {
                         class: "btn btn-danger",
-                        onclick: move |_| {
-                            // Simple confirmation using console for now
-                            web_sys::console::log_1(&"Delete clicked".into());
-                            on_delete.call(snippet_id_for_delete.clone());
-                        },
+                        onclick: move |_| confirming_delete.set(true),
                         span { class: "btn-icon", "🗑️" }
                         "Delete"
                     }
@@ -129,10 +168,23 @@ pub fn SnippetDetail(
                     }
                 }
 
+                if confirming_delete() {
+                    ConfirmDialog {
+                        title: "Delete snippet?",
+                        message: "\"{snippet.title}\" will be moved to the trash.",
+                        confirm_label: "Delete",
+                        on_confirm: move |_| {
+                            confirming_delete.set(false);
+                            on_delete.call(snippet_id_for_delete.clone());
+                        },
+                        on_cancel: move |_| confirming_delete.set(false),
+                    }
+                }
+
                 // Meta info
                 div { class: "detail-meta",
-                    p { "Created: {snippet.created_at}" }
-                    p { "Updated: {snippet.updated_at}" }
+                    p { "Created: " RelativeTime { at: snippet.created_at } }
+                    p { "Updated: " RelativeTime { at: snippet.updated_at } }
                 }
             }
         }
@@ -0,0 +1,171 @@
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use crate::settings::TagRules;
+use crate::tags;
+
+/// How many existing tags are suggested at once.
+const MAX_SUGGESTIONS: usize = 6;
+
+/// Edits a list of tags as chips, suggesting tags already in the vault.
+///
+/// Typing a separator, pressing Enter or leaving the field adds what was
+/// typed; Backspace in an empty field removes the last chip.
+#[component]
+pub fn TagEditor(
+    tags: Vec<String>,
+    /// Existing tags to suggest, most used first.
+    known_tags: Vec<String>,
+    rules: TagRules,
+    on_change: EventHandler<Vec<String>>,
+) -> Element {
+    let mut draft = use_signal(String::new);
+    let mut highlighted = use_signal(|| None::<usize>);
+
+    let typed = tags::normalize(&draft(), &rules).unwrap_or_default();
+    let suggestions: Vec<String> = if typed.is_empty() {
+        Vec::new()
+    } else {
+        let (mut prefixed, contained): (Vec<&String>, Vec<&String>) = known_tags
+            .iter()
+            .filter(|tag| !tags.contains(tag) && **tag != typed && tag.contains(&typed))
+            .partition(|tag| tag.starts_with(&typed));
+        prefixed.extend(contained);
+        prefixed.into_iter().take(MAX_SUGGESTIONS).cloned().collect()
+    };
+
+    // Adds each of `new` that is not already there and clears the draft.
+    let add = {
+        let tags = tags.clone();
+        move |new: Vec<String>| {
+            let mut updated = tags.clone();
+            for tag in new {
+                if !updated.contains(&tag) {
+                    updated.push(tag);
+                }
+            }
+            draft.set(String::new());
+            highlighted.set(None);
+            if updated != tags {
+                on_change.call(updated);
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "tag-editor",
+            div { class: "tag-chips",
+                for (index, tag) in tags.iter().enumerate() {
+                    span { key: "{tag}", class: "tag-chip",
+                        "#{tag}"
+                        button {
+                            class: "tag-chip-remove",
+                            r#type: "button",
+                            title: "Remove tag",
+                            onclick: {
+                                let tags = tags.clone();
+                                move |_| {
+                                    let mut updated = tags.clone();
+                                    updated.remove(index);
+                                    on_change.call(updated);
+                                }
+                            },
+                            "×"
+                        }
+                    }
+                }
+                input {
+                    class: "tag-input",
+                    r#type: "text",
+                    placeholder: if tags.is_empty() { "Add tags..." } else { "" },
+                    value: "{draft}",
+                    oninput: {
+                        let rules = rules.clone();
+                        let mut add = add.clone();
+                        move |e: FormEvent| {
+                            let value = e.value();
+                            let (finished, rest) = tags::split(&value, &rules);
+                            if finished.is_empty() {
+                                draft.set(value);
+                                highlighted.set(None);
+                            } else {
+                                add(finished);
+                                draft.set(rest.to_string());
+                            }
+                        }
+                    },
+                    onkeydown: {
+                        let tags = tags.clone();
+                        let suggestions = suggestions.clone();
+                        let typed = typed.clone();
+                        let mut add = add.clone();
+                        move |e: KeyboardEvent| match e.key() {
+                            Key::Enter => {
+                                // Enter finishes a tag rather than submitting the form.
+                                e.prevent_default();
+                                match highlighted().and_then(|i| suggestions.get(i)) {
+                                    Some(suggestion) => add(vec![suggestion.clone()]),
+                                    None if !typed.is_empty() => add(vec![typed.clone()]),
+                                    None => {}
+                                }
+                            }
+                            Key::Tab if highlighted().is_some() => {
+                                e.prevent_default();
+                                if let Some(suggestion) = highlighted().and_then(|i| suggestions.get(i)) {
+                                    add(vec![suggestion.clone()]);
+                                }
+                            }
+                            Key::ArrowDown if !suggestions.is_empty() => {
+                                e.prevent_default();
+                                let next = highlighted().map_or(0, |i| (i + 1).min(suggestions.len() - 1));
+                                highlighted.set(Some(next));
+                            }
+                            Key::ArrowUp if !suggestions.is_empty() => {
+                                e.prevent_default();
+                                highlighted.set(highlighted().and_then(|i| i.checked_sub(1)));
+                            }
+                            Key::Escape => highlighted.set(None),
+                            Key::Backspace if draft().is_empty() && !tags.is_empty() => {
+                                let mut updated = tags.clone();
+                                updated.pop();
+                                on_change.call(updated);
+                            }
+                            _ => {}
+                        }
+                    },
+                    onfocusout: {
+                        let typed = typed.clone();
+                        let mut add = add.clone();
+                        move |_| {
+                            if !typed.is_empty() {
+                                add(vec![typed.clone()]);
+                            }
+                        }
+                    },
+                }
+            }
+
+            if !suggestions.is_empty() {
+                div { class: "tag-suggestions",
+                    for (index, suggestion) in suggestions.iter().enumerate() {
+                        button {
+                            key: "{suggestion}",
+                            class: if highlighted() == Some(index) { "tag-suggestion active" } else { "tag-suggestion" },
+                            r#type: "button",
+                            // Picked on mousedown, before the input's focusout adds the draft.
+                            onmousedown: {
+                                let suggestion = suggestion.clone();
+                                let mut add = add.clone();
+                                move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    add(vec![suggestion.clone()]);
+                                }
+                            },
+                            "#{suggestion}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
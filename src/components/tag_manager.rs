@@ -0,0 +1,210 @@
+#![allow(non_snake_case)]
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+
+use crate::settings::TagRules;
+use crate::tags;
+use super::confirm_dialog::ConfirmDialog;
+
+/// Lists every tag with its usage count for renaming, merging and deleting
+/// across the whole vault.
+#[component]
+pub fn TagManagerScreen(
+    /// Each tag and how many snippets carry it.
+    tags: Vec<(String, usize)>,
+    /// How new names are cleaned up, as in the tag editor.
+    rules: TagRules,
+    /// The old and new name.
+    on_rename: EventHandler<(String, String)>,
+    /// The tags to merge and the tag they become.
+    on_merge: EventHandler<(Vec<String>, String)>,
+    on_delete: EventHandler<String>,
+) -> Element {
+    let mut filter = use_signal(String::new);
+    let mut selected = use_signal(HashSet::<String>::new);
+    // The tag being renamed and the name typed so far.
+    let mut renaming = use_signal(|| None::<(String, String)>);
+    let mut merge_target = use_signal(String::new);
+    let mut pending_delete = use_signal(|| None::<(String, usize)>);
+
+    let needle = filter().trim().to_lowercase();
+    let shown: Vec<(String, usize)> = tags
+        .iter()
+        .filter(|(tag, _)| tag.to_lowercase().contains(&needle))
+        .cloned()
+        .collect();
+    // In usage order, so the default merge target is the most used tag.
+    let selected_tags: Vec<String> = tags
+        .iter()
+        .map(|(tag, _)| tag)
+        .filter(|tag| selected.read().contains(*tag))
+        .cloned()
+        .collect();
+    let merge_into = if merge_target().trim().is_empty() {
+        selected_tags.first().cloned().unwrap_or_default()
+    } else {
+        merge_target().trim().to_string()
+    };
+
+    rsx! {
+        div { class: "tag-manager",
+            h2 { class: "form-title", "Tags" }
+
+            if tags.is_empty() {
+                div { class: "empty-state",
+                    div { class: "empty-icon", "🏷️" }
+                    h3 { "No tags yet" }
+                    p { "Tags you add to snippets show up here." }
+                }
+            } else {
+                div { class: "search-bar",
+                    input {
+                        class: "search-input",
+                        r#type: "search",
+                        placeholder: "Filter {tags.len()} tags...",
+                        value: "{filter}",
+                        oninput: move |e| filter.set(e.value()),
+                    }
+                    span { class: "search-icon", "🏷️" }
+                }
+
+                if selected_tags.len() > 1 {
+                    form {
+                        class: "tag-merge-bar",
+                        onsubmit: {
+                            let from = selected_tags.clone();
+                            let into = tags::normalize(&merge_into, &rules);
+                            move |e: FormEvent| {
+                                e.prevent_default();
+                                let Some(into) = into.clone() else {
+                                    return;
+                                };
+                                selected.write().clear();
+                                merge_target.set(String::new());
+                                on_merge.call((from.clone(), into));
+                            }
+                        },
+                        span { "Merge {selected_tags.len()} tags into" }
+                        input {
+                            class: "form-input",
+                            r#type: "text",
+                            value: "{merge_into}",
+                            oninput: move |e| merge_target.set(e.value()),
+                        }
+                        button { class: "btn btn-primary btn-sm", r#type: "submit", "Merge" }
+                        button {
+                            class: "btn btn-ghost btn-sm",
+                            r#type: "button",
+                            onclick: move |_| selected.write().clear(),
+                            "Clear"
+                        }
+                    }
+                }
+
+                div { class: "tag-list",
+                    for (tag, count) in shown {
+                        div {
+                            key: "{tag}",
+                            class: "tag-row",
+                            input {
+                                r#type: "checkbox",
+                                checked: selected.read().contains(&tag),
+                                onchange: {
+                                    let tag = tag.clone();
+                                    move |_| {
+                                        let mut selected = selected.write();
+                                        if !selected.remove(&tag) {
+                                            selected.insert(tag.clone());
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some((_, name)) = renaming().filter(|(old, _)| *old == tag) {
+                                form {
+                                    class: "tag-rename",
+                                    onsubmit: {
+                                        let tag = tag.clone();
+                                        let rules = rules.clone();
+                                        move |e: FormEvent| {
+                                            e.prevent_default();
+                                            let Some((_, name)) = renaming.take() else {
+                                                return;
+                                            };
+                                            match tags::normalize(&name, &rules) {
+                                                Some(name) if name != tag => on_rename.call((tag.clone(), name)),
+                                                _ => {}
+                                            }
+                                        }
+                                    },
+                                    input {
+                                        class: "form-input",
+                                        r#type: "text",
+                                        value: "{name}",
+                                        onmounted: move |e| async move {
+                                            let _ = e.set_focus(true).await;
+                                        },
+                                        oninput: {
+                                            let tag = tag.clone();
+                                            move |e: FormEvent| renaming.set(Some((tag.clone(), e.value())))
+                                        },
+                                    }
+                                    button { class: "btn btn-primary btn-sm", r#type: "submit", "Rename" }
+                                    button {
+                                        class: "btn btn-ghost btn-sm",
+                                        r#type: "button",
+                                        onclick: move |_| renaming.set(None),
+                                        "Cancel"
+                                    }
+                                }
+                            } else {
+                                span { class: "tag", "#{tag}" }
+                                span { class: "tag-count",
+                                    if count == 1 { "1 snippet" } else { "{count} snippets" }
+                                }
+                                div { class: "card-actions",
+                                    button {
+                                        class: "action-btn",
+                                        title: "Rename, along with the tags nested below it",
+                                        onclick: {
+                                            let tag = tag.clone();
+                                            move |_| renaming.set(Some((tag.clone(), tag.clone())))
+                                        },
+                                        "✏️"
+                                    }
+                                    button {
+                                        class: "action-btn delete",
+                                        title: "Delete everywhere",
+                                        onclick: {
+                                            let tag = tag.clone();
+                                            move |_| pending_delete.set(Some((tag.clone(), count)))
+                                        },
+                                        "🗑️"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some((tag, count)) = pending_delete() {
+                ConfirmDialog {
+                    title: "Delete tag?",
+                    message: if count == 1 {
+                        format!("#{tag} will be removed from 1 snippet.")
+                    } else {
+                        format!("#{tag} will be removed from {count} snippets.")
+                    },
+                    confirm_label: "Delete tag",
+                    on_confirm: move |_| {
+                        pending_delete.set(None);
+                        selected.write().remove(&tag);
+                        on_delete.call(tag.clone());
+                    },
+                    on_cancel: move |_| pending_delete.set(None),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#![allow(non_snake_case)]
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+
+use crate::tags::TagNode;
+
+/// The tag hierarchy as a collapsible tree; picking a level filters by it
+/// and everything below it.
+#[component]
+pub fn TagTree(
+    nodes: Vec<TagNode>,
+    /// The tag path currently filtered by, if any.
+    active: Option<String>,
+    on_select: EventHandler<String>,
+) -> Element {
+    let collapsed = use_signal(HashSet::<String>::new);
+
+    rsx! {
+        aside { class: "collection-sidebar tag-tree",
+            div { class: "sidebar-header",
+                h3 { class: "form-section-title", "Tags" }
+            }
+            if nodes.is_empty() {
+                p { class: "form-hint", "No tags yet. Use / in a tag, like web/react, to nest it." }
+            }
+            TagLevel { nodes, depth: 0, active, collapsed, on_select }
+        }
+    }
+}
+
+#[component]
+fn TagLevel(
+    nodes: Vec<TagNode>,
+    depth: usize,
+    active: Option<String>,
+    collapsed: Signal<HashSet<String>>,
+    on_select: EventHandler<String>,
+) -> Element {
+    let indent = format!("padding-left: {}rem", 0.75 + depth as f32);
+
+    rsx! {
+        for node in nodes {
+            div { key: "{node.path}",
+                div {
+                    class: if active.as_deref() == Some(node.path.as_str()) { "collection-row active" } else { "collection-row" },
+                    style: "{indent}",
+                    title: "#{node.path}",
+                    onclick: {
+                        let path = node.path.clone();
+                        move |_| on_select.call(path.clone())
+                    },
+                    if !node.children.is_empty() {
+                        button {
+                            class: "collection-toggle",
+                            onclick: {
+                                let path = node.path.clone();
+                                move |e: MouseEvent| {
+                                    e.stop_propagation();
+                                    let mut collapsed = collapsed;
+                                    let mut set = collapsed.write();
+                                    if !set.remove(&path) {
+                                        set.insert(path.clone());
+                                    }
+                                }
+                            },
+                            if collapsed.read().contains(&node.path) { "▸" } else { "▾" }
+                        }
+                    }
+                    span { class: "collection-name", "#{node.name}" }
+                    span { class: "collection-count", "{node.count}" }
+                }
+                if !node.children.is_empty() && !collapsed.read().contains(&node.path) {
+                    TagLevel {
+                        nodes: node.children.clone(),
+                        depth: depth + 1,
+                        active: active.clone(),
+                        collapsed,
+                        on_select,
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+#![allow(non_snake_case)]
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+
+use crate::{time, AppState};
+
+/// How long a toast stays on screen before dismissing itself.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// Toasts with an action (undo) stay up longer so there is time to use it.
+const ACTION_TOAST_DURATION: Duration = Duration::from_secs(6);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+    pub action: Option<ToastAction>,
+}
+
+/// A button on a toast, such as "Undo".
+///
+/// A plain closure rather than an `EventHandler`, because the toast usually
+/// outlives the screen that showed it.
+#[derive(Clone)]
+pub struct ToastAction {
+    pub label: String,
+    run: Rc<dyn Fn()>,
+}
+
+impl PartialEq for ToastAction {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && Rc::ptr_eq(&self.run, &other.run)
+    }
+}
+
+impl fmt::Debug for ToastAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToastAction").field("label", &self.label).finish_non_exhaustive()
+    }
+}
+
+/// Handle for showing short status messages, shared through [`AppState`].
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    items: Signal<Vec<Toast>>,
+    next_id: Signal<u64>,
+}
+
+impl Toasts {
+    /// Must be called inside a component, as it creates signals.
+    pub fn new() -> Self {
+        Self {
+            items: Signal::new(Vec::new()),
+            next_id: Signal::new(0),
+        }
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.show(ToastKind::Success, message.into(), None);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.show(ToastKind::Error, message.into(), None);
+    }
+
+    /// A success toast with an "Undo" button that runs `undo` once.
+    pub fn undoable(&mut self, message: impl Into<String>, undo: impl Fn() + 'static) {
+        let action = ToastAction {
+            label: "Undo".into(),
+            run: Rc::new(undo),
+        };
+        self.show(ToastKind::Success, message.into(), Some(action));
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.write().retain(|toast| toast.id != id);
+    }
+
+    fn show(&mut self, kind: ToastKind, message: String, action: Option<ToastAction>) {
+        let id = *self.next_id.peek();
+        self.next_id.set(id + 1);
+        let duration = if action.is_some() { ACTION_TOAST_DURATION } else { TOAST_DURATION };
+        self.items.write().push(Toast { id, kind, message, action });
+
+        // Spawned on the root scope so the toast still goes away if the
+        // screen that showed it is navigated away from.
+        let mut toasts = *self;
+        spawn_forever(async move {
+            time::sleep(duration).await;
+            toasts.dismiss(id);
+        });
+    }
+}
+
+/// Renders the active toasts above the bottom navigation.
+#[component]
+pub fn ToastHost() -> Element {
+    let mut toasts = use_context::<AppState>().toasts;
+
+    rsx! {
+        div { class: "toast-host",
+            for toast in toasts.items.read().iter().cloned() {
+                div {
+                    key: "{toast.id}",
+                    class: match toast.kind {
+                        ToastKind::Success => "toast success",
+                        ToastKind::Error => "toast error",
+                    },
+                    onclick: move |_| toasts.dismiss(toast.id),
+                    span { "{toast.message}" }
+                    if let Some(action) = toast.action {
+                        button {
+                            class: "toast-action",
+                            onclick: move |e| {
+                                e.stop_propagation();
+                                toasts.dismiss(toast.id);
+                                (action.run)();
+                            },
+                            "{action.label}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(non_snake_case)]
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+
+use crate::Snippet;
+use super::confirm_dialog::ConfirmDialog;
+use super::relative_time::RelativeTime;
+
+/// Lists trashed snippets for restoring or deleting for good.
+///
+/// Every action hands over a list of ids so single and bulk operations go
+/// through the same handler.
+#[component]
+pub fn TrashScreen(
+    snippets: Vec<Snippet>,
+    /// Days a snippet stays in the trash before it is purged; 0 keeps it forever.
+    retention_days: u32,
+    on_restore: EventHandler<Vec<String>>,
+    on_delete_forever: EventHandler<Vec<String>>,
+) -> Element {
+    let mut selected = use_signal(HashSet::<String>::new);
+    // Ids waiting for the "delete forever" confirmation.
+    let mut pending_delete = use_signal(|| None::<Vec<String>>);
+
+    let all_ids: Vec<String> = snippets.iter().map(|s| s.id.clone()).collect();
+    let selected_ids: Vec<String> = all_ids
+        .iter()
+        .filter(|id| selected.read().contains(*id))
+        .cloned()
+        .collect();
+
+    rsx! {
+        div { class: "trash-screen",
+            div { class: "trash-header",
+                h2 { class: "form-title", "Trash" }
+                p { class: "trash-retention",
+                    if retention_days == 0 {
+                        "Snippets stay in the trash until you delete them."
+                    } else {
+                        "Snippets are deleted for good {retention_days} days after being trashed."
+                    }
+                }
+            }
+
+            if snippets.is_empty() {
+                div { class: "empty-state",
+                    div { class: "empty-icon", "🗑️" }
+                    h3 { "Trash is empty" }
+                    p { "Deleted snippets show up here until they are purged." }
+                }
+            } else {
+                div { class: "trash-toolbar",
+                    if selected_ids.is_empty() {
+                        button {
+                            class: "btn btn-secondary btn-sm",
+                            onclick: {
+                                let ids = all_ids.clone();
+                                move |_| on_restore.call(ids.clone())
+                            },
+                            "Restore all"
+                        }
+                        button {
+                            class: "btn btn-danger btn-sm",
+                            onclick: {
+                                let ids = all_ids.clone();
+                                move |_| pending_delete.set(Some(ids.clone()))
+                            },
+                            "Empty trash"
+                        }
+                    } else {
+                        button {
+                            class: "btn btn-secondary btn-sm",
+                            onclick: {
+                                let ids = selected_ids.clone();
+                                move |_| {
+                                    selected.write().clear();
+                                    on_restore.call(ids.clone());
+                                }
+                            },
+                            "Restore selected ({selected_ids.len()})"
+                        }
+                        button {
+                            class: "btn btn-danger btn-sm",
+                            onclick: {
+                                let ids = selected_ids.clone();
+                                move |_| pending_delete.set(Some(ids.clone()))
+                            },
+                            "Delete selected"
+                        }
+                    }
+                }
+
+                div { class: "trash-list",
+                    for snippet in snippets {
+                        div {
+                            key: "{snippet.id}",
+                            class: "trash-item",
+                            input {
+                                r#type: "checkbox",
+                                checked: selected.read().contains(&snippet.id),
+                                onchange: {
+                                    let id = snippet.id.clone();
+                                    move |_| {
+                                        let mut selected = selected.write();
+                                        if !selected.remove(&id) {
+                                            selected.insert(id.clone());
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "trash-item-info",
+                                span { class: "card-title", "{snippet.title}" }
+                                span { class: "language-badge {snippet.language}", "{snippet.language}" }
+                                if let Some(deleted_at) = snippet.deleted_at {
+                                    span { class: "date", "Deleted " RelativeTime { at: deleted_at } }
+                                }
+                            }
+                            div { class: "card-actions",
+                                button {
+                                    class: "action-btn",
+                                    title: "Restore",
+                                    onclick: {
+                                        let id = snippet.id.clone();
+                                        move |_| on_restore.call(vec![id.clone()])
+                                    },
+                                    "↩️"
+                                }
+                                button {
+                                    class: "action-btn delete",
+                                    title: "Delete forever",
+                                    onclick: {
+                                        let id = snippet.id.clone();
+                                        move |_| pending_delete.set(Some(vec![id.clone()]))
+                                    },
+                                    "✖"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(ids) = pending_delete() {
+                ConfirmDialog {
+                    title: "Delete forever?",
+                    message: if ids.len() == 1 {
+                        "This snippet will be permanently deleted. This cannot be undone.".to_string()
+                    } else {
+                        format!("{} snippets will be permanently deleted. This cannot be undone.", ids.len())
+                    },
+                    confirm_label: "Delete forever",
+                    on_confirm: move |_| {
+                        pending_delete.set(None);
+                        selected.write().retain(|id| !ids.contains(id));
+                        on_delete_forever.call(ids.clone());
+                    },
+                    on_cancel: move |_| pending_delete.set(None),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Line-level diffs between two texts.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineChange {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine<'a> {
+    pub change: LineChange,
+    pub text: &'a str,
+}
+
+/// Diffs `old` against `new` line by line using a longest common
+/// subsequence, listing removals before additions within each changed run.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // An edit usually touches a few lines in the middle, so the lines both
+    // sides share at either end are kept out of the quadratic LCS table.
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let unchanged = |text| DiffLine { change: LineChange::Unchanged, text };
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    lines.extend(old[..prefix].iter().copied().map(unchanged));
+    diff_middle(&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix], &mut lines);
+    lines.extend(old[old.len() - suffix..].iter().copied().map(unchanged));
+    lines
+}
+
+/// Appends the LCS diff of `old` against `new` to `lines`.
+fn diff_middle<'a>(old: &[&'a str], new: &[&'a str], lines: &mut Vec<DiffLine<'a>>) {
+    // lcs[i][j] is the LCS length of old[i..] and new[j..].
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(DiffLine { change: LineChange::Unchanged, text: old[i] });
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(DiffLine { change: LineChange::Removed, text: old[i] });
+            i += 1;
+        } else {
+            lines.push(DiffLine { change: LineChange::Added, text: new[j] });
+            j += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The diff as `" line"`, `"+line"` and `"-line"`, one entry per line.
+    fn render(old: &str, new: &str) -> Vec<String> {
+        diff_lines(old, new)
+            .into_iter()
+            .map(|line| {
+                let mark = match line.change {
+                    LineChange::Unchanged => ' ',
+                    LineChange::Added => '+',
+                    LineChange::Removed => '-',
+                };
+                format!("{mark}{}", line.text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn diffs_line_by_line() {
+        let cases: &[(&str, &str, &str, &[&str])] = &[
+            ("both empty", "", "", &[]),
+            ("unchanged", "a\nb", "a\nb", &[" a", " b"]),
+            ("old empty", "", "a\nb", &["+a", "+b"]),
+            ("new empty", "a\nb", "", &["-a", "-b"]),
+            ("insert in the middle", "a\nc", "a\nb\nc", &[" a", "+b", " c"]),
+            ("insert at the start", "b\nc", "a\nb\nc", &["+a", " b", " c"]),
+            ("insert at the end", "a\nb", "a\nb\nc", &[" a", " b", "+c"]),
+            ("delete in the middle", "a\nb\nc", "a\nc", &[" a", "-b", " c"]),
+            ("delete at the end", "a\nb\nc", "a\nb", &[" a", " b", "-c"]),
+            ("replace", "a\nb\nc", "a\nx\nc", &[" a", "-b", "+x", " c"]),
+            ("replace a run", "a\nb\nc\nd", "a\nx\ny\nd", &[" a", "-b", "-c", "+x", "+y", " d"]),
+            ("replace everything", "a\nb", "x\ny", &["-a", "-b", "+x", "+y"]),
+            ("moved line", "a\nb\nc", "b\nc\na", &["-a", " b", " c", "+a"]),
+            ("repeated lines", "a\na", "a\na\na", &[" a", " a", "+a"]),
+            ("trailing newline ignored", "a\n", "a", &[" a"]),
+        ];
+        for (name, old, new, expected) in cases {
+            assert_eq!(render(old, new), *expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn keeps_a_change_between_shared_ends() {
+        let old: String = (0..1000).map(|n| format!("line {n}\n")).collect();
+        let new = old.replace("line 500\n", "line 500 changed\n");
+        let diff = diff_lines(&old, &new);
+        assert_eq!(diff.len(), 1001);
+        let changed: Vec<_> = diff.iter().filter(|line| line.change != LineChange::Unchanged).collect();
+        assert_eq!(changed, [
+            &DiffLine { change: LineChange::Removed, text: "line 500" },
+            &DiffLine { change: LineChange::Added, text: "line 500 changed" },
+        ]);
+    }
+}
@@ -0,0 +1,105 @@
+use super::{number_len, quoted_len, run_len, Token, TokenKind, Tokens};
+
+/// At-rules whose blocks hold more rules rather than declarations.
+const GROUPING_AT_RULES: &[&str] = &["@media", "@supports", "@layer", "@container", "@document"];
+
+/// Tokenizes CSS: selectors outside declaration blocks, property names
+/// inside them, at-rules, strings, numbers with units, hex colours and
+/// comments.
+pub fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let mut tokens = Tokens::new(code);
+    // One entry per open `{`: whether that block holds rules (selectors)
+    // rather than declarations.
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut grouping_prelude = false;
+    let mut i = 0;
+    while i < code.len() {
+        let rest = &code[i..];
+        let first = rest.chars().next().unwrap_or_default();
+        let in_selectors = blocks.last().copied().unwrap_or(true);
+        let (kind, len) = if let Some(body) = rest.strip_prefix("/*") {
+            let len = body.find("*/").map_or(rest.len(), |end| end + 4);
+            (TokenKind::Comment, len)
+        } else if first.is_whitespace() {
+            (TokenKind::Plain, run_len(rest, char::is_whitespace))
+        } else if first == '"' || first == '\'' {
+            (TokenKind::String, quoted_len(rest, first, false))
+        } else if first == '@' {
+            let len = 1 + run_len(&rest[1..], is_ident_char);
+            grouping_prelude = GROUPING_AT_RULES.contains(&&rest[..len]);
+            (TokenKind::Keyword, len)
+        } else if first == '#' && !in_selectors {
+            (TokenKind::Number, 1 + run_len(&rest[1..], |c| c.is_ascii_alphanumeric()))
+        } else if first.is_ascii_digit() || (first == '.' && starts_with_digit(&rest[1..])) {
+            let len = if first == '.' { 1 + number_len(&rest[1..]) } else { number_len(rest) };
+            // Units such as `px` and `%`.
+            (TokenKind::Number, len + run_len(&rest[len..], |c| c.is_alphabetic() || c == '%'))
+        } else if in_selectors && (is_ident_char(first) || matches!(first, '.' | '#' | ':' | '*')) {
+            (TokenKind::Tag, run_len(rest, |c| is_ident_char(c) || matches!(c, '.' | '#' | ':' | '*' | '(' | ')')))
+        } else if is_ident_char(first) {
+            let len = run_len(rest, is_ident_char);
+            let is_property = rest[len..].trim_start().starts_with(':');
+            (if is_property { TokenKind::Property } else { TokenKind::Plain }, len)
+        } else {
+            match first {
+                '{' => blocks.push(std::mem::take(&mut grouping_prelude)),
+                '}' => {
+                    blocks.pop();
+                }
+                ';' => grouping_prelude = false,
+                _ => {}
+            }
+            (TokenKind::Punctuation, first.len_utf8())
+        };
+        tokens.push(kind, i, i + len);
+        i += len;
+    }
+    tokens.finish()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn starts_with_digit(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The texts of the tokens of `kind`.
+    fn texts(code: &str, kind: TokenKind) -> Vec<&str> {
+        tokenize(code).into_iter().filter(|token| token.kind == kind).map(|token| token.text).collect()
+    }
+
+    #[test]
+    fn rules_inside_media_blocks_keep_their_selectors() {
+        let code = "@media (max-width: 600px) {\n  .card, a:hover { color: #fff; }\n}\nbody { margin: 0 }";
+        assert_eq!(texts(code, TokenKind::Keyword), ["@media"]);
+        let tags = texts(code, TokenKind::Tag);
+        assert_eq!(tags[tags.len() - 3..], [".card", "a:hover", "body"]);
+        assert_eq!(texts(code, TokenKind::Property), ["color", "margin"]);
+        assert_eq!(texts(code, TokenKind::Number), ["600px", "#fff", "0"]);
+    }
+
+    #[test]
+    fn other_at_rules_hold_declarations() {
+        let code = "@import \"a.css\";\n@font-face { font-family: Inter; src: url(x.woff2) }";
+        assert_eq!(texts(code, TokenKind::Property), ["font-family", "src"]);
+        assert_eq!(texts(code, TokenKind::Tag), Vec::<&str>::new());
+        assert_eq!(texts(code, TokenKind::String), ["\"a.css\""]);
+    }
+
+    #[test]
+    fn numbers_take_their_units() {
+        let code = "p { width: 50%; line-height: .5em; }";
+        assert_eq!(texts(code, TokenKind::Number), ["50%", ".5em"]);
+    }
+
+    #[test]
+    fn unterminated_comments_run_to_the_end() {
+        assert_eq!(texts("a { } /* open\n b { }", TokenKind::Comment), ["/* open\n b { }"]);
+    }
+}
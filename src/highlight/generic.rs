@@ -0,0 +1,163 @@
+use super::languages::LanguageSpec;
+use super::{number_len, quoted_len, run_len, Token, TokenKind, Tokens};
+
+/// Tokenizes the C-like and scripting languages described by a [`LanguageSpec`].
+pub fn tokenize<'a>(spec: &LanguageSpec, code: &'a str) -> Vec<Token<'a>> {
+    let mut tokens = Tokens::new(code);
+    let mut i = 0;
+    while i < code.len() {
+        let (kind, len) = next_token(spec, &code[i..]);
+        tokens.push(kind, i, i + len);
+        i += len;
+    }
+    tokens.finish()
+}
+
+fn next_token(spec: &LanguageSpec, rest: &str) -> (TokenKind, usize) {
+    let first = rest.chars().next().unwrap_or_default();
+
+    if first.is_whitespace() {
+        return (TokenKind::Plain, run_len(rest, char::is_whitespace));
+    }
+    if spec.line_comments.iter().any(|prefix| rest.starts_with(prefix)) {
+        return (TokenKind::Comment, rest.find('\n').unwrap_or(rest.len()));
+    }
+    if let Some((open, close)) = spec.block_comment {
+        if let Some(body) = rest.strip_prefix(open) {
+            let len = body
+                .find(close)
+                .map_or(rest.len(), |end| open.len() + end + close.len());
+            return (TokenKind::Comment, len);
+        }
+    }
+    if spec.triple_quotes {
+        for triple in ["\"\"\"", "'''"] {
+            if rest.starts_with(triple) {
+                let len = rest[3..].find(triple).map_or(rest.len(), |end| end + 6);
+                return (TokenKind::String, len);
+            }
+        }
+    }
+    if spec.quotes.contains(&first) {
+        if first == '\'' && spec.lifetimes && !is_char_literal(rest) {
+            // A lifetime such as `'a` or `'static`.
+            let len = 1 + run_len(&rest[1..], is_ident_char);
+            return (TokenKind::Type, len);
+        }
+        let multiline = spec.multiline_quotes.contains(&first);
+        return (TokenKind::String, quoted_len(rest, first, multiline));
+    }
+    if first.is_ascii_digit() {
+        return (TokenKind::Number, number_len(rest));
+    }
+    if is_ident_start(first) || spec.ident_chars.contains(&first) {
+        let len = run_len(rest, |c| is_ident_char(c) || spec.ident_chars.contains(&c));
+        return word(spec, rest, len);
+    }
+    (TokenKind::Punctuation, first.len_utf8())
+}
+
+/// Classifies the identifier `rest[..len]` by what it is and what follows it.
+fn word(spec: &LanguageSpec, rest: &str, len: usize) -> (TokenKind, usize) {
+    let text = &rest[..len];
+    if spec.keywords.contains(&text) {
+        return (TokenKind::Keyword, len);
+    }
+    if spec.literals.contains(&text) {
+        return (TokenKind::Literal, len);
+    }
+    let after = &rest[len..];
+    if spec.macros && after.starts_with('!') && !after.starts_with("!=") {
+        return (TokenKind::Function, len + 1);
+    }
+    if after.trim_start_matches([' ', '\t']).starts_with('(') {
+        return (TokenKind::Function, len);
+    }
+    if text.starts_with(|c: char| c.is_uppercase()) {
+        return (TokenKind::Type, len);
+    }
+    (TokenKind::Plain, len)
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// `'x'` or `'\n'` rather than a lifetime.
+fn is_char_literal(rest: &str) -> bool {
+    let mut chars = rest.chars().skip(1);
+    match chars.next() {
+        Some('\\') => true,
+        Some(_) => chars.next() == Some('\''),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::languages;
+    use super::*;
+
+    fn tokens<'a>(language: &str, code: &'a str) -> Vec<(TokenKind, &'a str)> {
+        let spec = languages::spec(language).unwrap();
+        tokenize(spec, code).into_iter().map(|token| (token.kind, token.text)).collect()
+    }
+
+    /// The texts of the tokens of `kind`.
+    fn texts<'a>(language: &str, code: &'a str, kind: TokenKind) -> Vec<&'a str> {
+        tokens(language, code).into_iter().filter(|(k, _)| *k == kind).map(|(_, text)| text).collect()
+    }
+
+    #[test]
+    fn rust_lifetimes_are_not_char_literals() {
+        let code = "fn first<'a>(s: &'a str, c: char) -> &'static str { if c == 'x' || c == '\\n' { s } else { \"\" } }";
+        assert_eq!(texts("rust", code, TokenKind::Type), ["'a", "'a", "'static"]);
+        assert_eq!(texts("rust", code, TokenKind::String), ["'x'", "'\\n'", "\"\""]);
+    }
+
+    #[test]
+    fn quotes_are_char_literals_outside_rust() {
+        assert_eq!(texts("c", "char c = 'a';", TokenKind::String), ["'a'"]);
+        assert_eq!(texts("javascript", "f('abc')", TokenKind::String), ["'abc'"]);
+    }
+
+    #[test]
+    fn a_range_is_two_numbers() {
+        assert_eq!(
+            tokens("rust", "0..10"),
+            [(TokenKind::Number, "0"), (TokenKind::Punctuation, ".."), (TokenKind::Number, "10")]
+        );
+        assert_eq!(texts("rust", "let x = 1.5 + 0xff_u8;", TokenKind::Number), ["1.5", "0xff_u8"]);
+    }
+
+    #[test]
+    fn unterminated_strings_stop_at_the_line_end_unless_multiline() {
+        assert_eq!(texts("c", "s = \"open\nnext();", TokenKind::String), ["\"open"]);
+        assert_eq!(texts("c", "s = \"open\nnext();", TokenKind::Function), ["next"]);
+        assert_eq!(texts("rust", "s = \"open\nnext();", TokenKind::String), ["\"open\nnext();"]);
+        assert_eq!(texts("javascript", "`open\nnext()", TokenKind::String), ["`open\nnext()"]);
+        assert_eq!(texts("python", "'''open\nnext()", TokenKind::String), ["'''open\nnext()"]);
+    }
+
+    #[test]
+    fn unterminated_comments_run_to_the_end() {
+        assert_eq!(tokens("rust", "x /* open\nfn"), [
+            (TokenKind::Plain, "x "),
+            (TokenKind::Comment, "/* open\nfn"),
+        ]);
+        assert_eq!(texts("python", "x # note", TokenKind::Comment), ["# note"]);
+        assert_eq!(texts("go", "// a\nfunc", TokenKind::Keyword), ["func"]);
+    }
+
+    #[test]
+    fn words_are_classified_by_what_follows() {
+        let code = "let v = Vec::new(); println!(\"{}\", v != None);";
+        assert_eq!(texts("rust", code, TokenKind::Function), ["new", "println!"]);
+        assert_eq!(texts("rust", code, TokenKind::Type), ["Vec"]);
+        assert_eq!(texts("rust", code, TokenKind::Literal), ["None"]);
+    }
+}
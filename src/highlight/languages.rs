@@ -0,0 +1,231 @@
+/// How the generic tokenizer should read one C-like or scripting language.
+pub struct LanguageSpec {
+    pub keywords: &'static [&'static str],
+    pub literals: &'static [&'static str],
+    pub line_comments: &'static [&'static str],
+    pub block_comment: Option<(&'static str, &'static str)>,
+    /// Characters that open (and close) a string literal.
+    pub quotes: &'static [char],
+    /// Python-style `"""` / `'''` strings.
+    pub triple_quotes: bool,
+    /// Quote characters whose strings may span lines (JS template literals).
+    pub multiline_quotes: &'static [char],
+    /// Extra characters allowed in identifiers, such as `$` in JS and PHP.
+    pub ident_chars: &'static [char],
+    /// `'a` is a Rust lifetime rather than an unterminated char literal.
+    pub lifetimes: bool,
+    /// `name!(...)` is a Rust macro call.
+    pub macros: bool,
+}
+
+const C_LIKE: LanguageSpec = LanguageSpec {
+    keywords: &[],
+    literals: &[],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    multiline_quotes: &[],
+    ident_chars: &[],
+    lifetimes: false,
+    macros: false,
+};
+
+const RUST: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    literals: &["true", "false", "None", "Some", "Ok", "Err"],
+    lifetimes: true,
+    macros: true,
+    quotes: &['"', '\''],
+    multiline_quotes: &['"'],
+    ..C_LIKE
+};
+
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "async", "await", "break", "case", "catch", "class", "const", "continue", "debugger",
+    "default", "delete", "do", "else", "export", "extends", "finally", "for", "from", "function",
+    "if", "import", "in", "instanceof", "let", "new", "of", "return", "static", "super", "switch",
+    "this", "throw", "try", "typeof", "var", "void", "while", "with", "yield",
+];
+
+const JAVASCRIPT: LanguageSpec = LanguageSpec {
+    keywords: JAVASCRIPT_KEYWORDS,
+    literals: &["true", "false", "null", "undefined", "NaN", "Infinity"],
+    quotes: &['"', '\'', '`'],
+    multiline_quotes: &['`'],
+    ident_chars: &['$'],
+    ..C_LIKE
+};
+
+const TYPESCRIPT: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract", "any", "as", "async", "await", "boolean", "break", "case", "catch", "class",
+        "const", "continue", "declare", "default", "delete", "do", "else", "enum", "export",
+        "extends", "finally", "for", "from", "function", "if", "implements", "import", "in",
+        "instanceof", "interface", "keyof", "let", "namespace", "never", "new", "number", "of",
+        "private", "protected", "public", "readonly", "return", "static", "string", "super",
+        "switch", "this", "throw", "try", "type", "typeof", "unknown", "var", "void", "while",
+        "yield",
+    ],
+    ..JAVASCRIPT
+};
+
+const PYTHON: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield",
+    ],
+    literals: &["True", "False", "None"],
+    line_comments: &["#"],
+    block_comment: None,
+    triple_quotes: true,
+    ..C_LIKE
+};
+
+const GO: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough",
+        "for", "func", "go", "goto", "if", "import", "interface", "map", "package", "range",
+        "return", "select", "struct", "switch", "type", "var",
+    ],
+    literals: &["true", "false", "nil", "iota"],
+    quotes: &['"', '\'', '`'],
+    multiline_quotes: &['`'],
+    ..C_LIKE
+};
+
+const JAVA: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class",
+        "const", "continue", "default", "do", "double", "else", "enum", "extends", "final",
+        "finally", "float", "for", "if", "implements", "import", "instanceof", "int", "interface",
+        "long", "new", "package", "private", "protected", "public", "record", "return", "short",
+        "static", "super", "switch", "synchronized", "this", "throw", "throws", "try", "var",
+        "void", "volatile", "while",
+    ],
+    literals: &["true", "false", "null"],
+    ..C_LIKE
+};
+
+const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "#include", "#define", "#ifdef", "#ifndef",
+    "#endif", "#if", "#else", "#pragma",
+];
+
+const C: LanguageSpec = LanguageSpec {
+    keywords: C_KEYWORDS,
+    literals: &["NULL", "true", "false"],
+    ident_chars: &['#'],
+    ..C_LIKE
+};
+
+const CPP: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "auto", "bool", "break", "case", "catch", "char", "class", "const", "constexpr",
+        "continue", "default", "delete", "do", "double", "else", "enum", "explicit", "extern",
+        "float", "for", "friend", "if", "inline", "int", "long", "namespace", "new", "noexcept",
+        "operator", "override", "private", "protected", "public", "return", "short", "signed",
+        "sizeof", "static", "struct", "switch", "template", "this", "throw", "try", "typedef",
+        "typename", "union", "unsigned", "using", "virtual", "void", "volatile", "while",
+        "#include", "#define", "#ifdef", "#ifndef", "#endif", "#if", "#else", "#pragma",
+    ],
+    literals: &["true", "false", "nullptr", "NULL"],
+    ..C
+};
+
+const SWIFT: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "as", "async", "await", "break", "case", "catch", "class", "continue", "default", "defer",
+        "do", "else", "enum", "extension", "fileprivate", "for", "func", "guard", "if", "import",
+        "in", "init", "inout", "internal", "is", "let", "mutating", "override", "private",
+        "protocol", "public", "repeat", "return", "self", "static", "struct", "switch", "throw",
+        "throws", "try", "var", "where", "while",
+    ],
+    literals: &["true", "false", "nil"],
+    quotes: &['"'],
+    triple_quotes: true,
+    ..C_LIKE
+};
+
+const KOTLIN: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "as", "break", "class", "companion", "continue", "data", "do", "else", "enum", "false",
+        "for", "fun", "if", "import", "in", "interface", "internal", "is", "lateinit", "object",
+        "open", "override", "package", "private", "protected", "public", "return", "sealed",
+        "super", "suspend", "this", "throw", "try", "val", "var", "when", "while",
+    ],
+    literals: &["true", "false", "null"],
+    triple_quotes: true,
+    ..C_LIKE
+};
+
+const DART: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract", "as", "async", "await", "break", "case", "catch", "class", "const",
+        "continue", "default", "do", "dynamic", "else", "enum", "extends", "factory", "final",
+        "finally", "for", "if", "implements", "import", "in", "is", "late", "library", "mixin",
+        "new", "required", "return", "static", "super", "switch", "this", "throw", "try", "var",
+        "void", "while", "with", "yield",
+    ],
+    literals: &["true", "false", "null"],
+    triple_quotes: true,
+    ident_chars: &['$'],
+    ..C_LIKE
+};
+
+const PHP: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "abstract", "as", "break", "case", "catch", "class", "const", "continue", "default", "do",
+        "echo", "else", "elseif", "extends", "final", "finally", "fn", "for", "foreach",
+        "function", "if", "implements", "include", "interface", "match", "namespace", "new",
+        "private", "protected", "public", "require", "return", "static", "switch", "throw",
+        "trait", "try", "use", "while",
+    ],
+    literals: &["true", "false", "null", "TRUE", "FALSE", "NULL"],
+    line_comments: &["//", "#"],
+    ident_chars: &['$'],
+    ..C_LIKE
+};
+
+const RUBY: LanguageSpec = LanguageSpec {
+    keywords: &[
+        "alias", "and", "begin", "break", "case", "class", "def", "defined?", "do", "else",
+        "elsif", "end", "ensure", "for", "if", "in", "module", "next", "not", "or", "redo",
+        "rescue", "retry", "return", "self", "super", "then", "unless", "until", "when", "while",
+        "yield",
+    ],
+    literals: &["true", "false", "nil"],
+    line_comments: &["#"],
+    block_comment: Some(("=begin", "=end")),
+    ident_chars: &['?', '!', '@'],
+    ..C_LIKE
+};
+
+pub fn spec(language: &str) -> Option<&'static LanguageSpec> {
+    Some(match language {
+        "rust" | "rs" => &RUST,
+        "javascript" | "js" | "jsx" => &JAVASCRIPT,
+        "typescript" | "ts" | "tsx" => &TYPESCRIPT,
+        "python" | "py" => &PYTHON,
+        "go" => &GO,
+        "java" => &JAVA,
+        "c" | "h" => &C,
+        "cpp" | "c++" | "hpp" => &CPP,
+        "swift" => &SWIFT,
+        "kotlin" | "kt" => &KOTLIN,
+        "dart" => &DART,
+        "php" => &PHP,
+        "ruby" | "rb" => &RUBY,
+        _ => return None,
+    })
+}
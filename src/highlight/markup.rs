@@ -0,0 +1,107 @@
+use super::{quoted_len, run_len, Token, TokenKind, Tokens};
+
+/// Tokenizes HTML: tags, attributes, attribute values and comments. Text
+/// between tags, including `<script>` and `<style>` bodies, stays plain.
+pub fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let mut tokens = Tokens::new(code);
+    let mut i = 0;
+    while i < code.len() {
+        let rest = &code[i..];
+        if rest.starts_with("<!--") {
+            let len = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            tokens.push(TokenKind::Comment, i, i + len);
+            i += len;
+        } else if is_tag_start(rest) {
+            i = tag(&mut tokens, code, i);
+        } else {
+            let skip = rest.chars().next().map_or(1, char::len_utf8);
+            let len = rest[skip..].find('<').map_or(rest.len(), |end| end + skip);
+            tokens.push(TokenKind::Plain, i, i + len);
+            i += len;
+        }
+    }
+    tokens.finish()
+}
+
+fn is_tag_start(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    chars.next() == Some('<')
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+/// Tokenizes the tag starting at `start` and returns where it ends.
+fn tag(tokens: &mut Tokens<'_>, code: &str, start: usize) -> usize {
+    let opener = if code[start..].starts_with("</") || code[start..].starts_with("<!") { 2 } else { 1 };
+    tokens.push(TokenKind::Punctuation, start, start + opener);
+    let mut i = start + opener;
+
+    let name = run_len(&code[i..], is_name_char);
+    tokens.push(TokenKind::Tag, i, i + name);
+    i += name;
+
+    while i < code.len() {
+        let rest = &code[i..];
+        let first = rest.chars().next().unwrap_or_default();
+        let (kind, len) = if rest.starts_with("/>") {
+            tokens.push(TokenKind::Punctuation, i, i + 2);
+            return i + 2;
+        } else if first == '>' {
+            tokens.push(TokenKind::Punctuation, i, i + 1);
+            return i + 1;
+        } else if first.is_whitespace() {
+            (TokenKind::Plain, run_len(rest, char::is_whitespace))
+        } else if first == '"' || first == '\'' {
+            (TokenKind::String, quoted_len(rest, first, true))
+        } else if is_name_char(first) {
+            (TokenKind::Attribute, run_len(rest, is_name_char))
+        } else {
+            (TokenKind::Punctuation, first.len_utf8())
+        };
+        tokens.push(kind, i, i + len);
+        i += len;
+    }
+    i
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(code: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(code).into_iter().map(|token| (token.kind, token.text)).collect()
+    }
+
+    #[test]
+    fn tags_split_into_names_attributes_and_values() {
+        assert_eq!(tokens("<a href='x'>hi</a>"), [
+            (TokenKind::Punctuation, "<"),
+            (TokenKind::Tag, "a"),
+            (TokenKind::Plain, " "),
+            (TokenKind::Attribute, "href"),
+            (TokenKind::Punctuation, "="),
+            (TokenKind::String, "'x'"),
+            (TokenKind::Punctuation, ">"),
+            (TokenKind::Plain, "hi"),
+            (TokenKind::Punctuation, "</"),
+            (TokenKind::Tag, "a"),
+            (TokenKind::Punctuation, ">"),
+        ]);
+    }
+
+    #[test]
+    fn a_lone_angle_bracket_is_text() {
+        assert_eq!(tokens("a < b <br/>")[0], (TokenKind::Plain, "a < b "));
+    }
+
+    #[test]
+    fn unterminated_comments_and_tags_run_to_the_end() {
+        assert_eq!(tokens("<!-- open <b>"), [(TokenKind::Comment, "<!-- open <b>")]);
+        assert_eq!(tokens("<img src=\"x").last(), Some(&(TokenKind::String, "\"x")));
+    }
+}
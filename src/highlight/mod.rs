@@ -0,0 +1,192 @@
+//! Syntax highlighting for snippet code.
+//!
+//! A small hand-written tokenizer rather than a grammar engine: it splits
+//! code into [`Token`]s good enough to colour keywords, strings, comments and
+//! friends for the languages offered in the snippet form, and it is cheap
+//! enough to run on every render. Unknown languages come back as a single
+//! plain token.
+
+mod css;
+mod generic;
+mod languages;
+mod markup;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    /// `true`, `null`, `None`, ...
+    Literal,
+    /// Capitalized identifiers, which are types in most of these languages.
+    Type,
+    /// An identifier directly followed by a call, or a Rust macro.
+    Function,
+    String,
+    Number,
+    Comment,
+    Punctuation,
+    /// HTML tag names and CSS selectors.
+    Tag,
+    /// HTML attribute names.
+    Attribute,
+    /// CSS property names.
+    Property,
+}
+
+impl TokenKind {
+    /// The CSS class the theme colours this kind with.
+    pub fn class(self) -> &'static str {
+        match self {
+            TokenKind::Plain => "tok-plain",
+            TokenKind::Keyword => "tok-keyword",
+            TokenKind::Literal => "tok-literal",
+            TokenKind::Type => "tok-type",
+            TokenKind::Function => "tok-function",
+            TokenKind::String => "tok-string",
+            TokenKind::Number => "tok-number",
+            TokenKind::Comment => "tok-comment",
+            TokenKind::Punctuation => "tok-punctuation",
+            TokenKind::Tag => "tok-tag",
+            TokenKind::Attribute => "tok-attribute",
+            TokenKind::Property => "tok-property",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+}
+
+/// Splits `code` into tokens. Concatenating the token texts gives back
+/// `code` exactly.
+pub fn highlight<'a>(language: &str, code: &'a str) -> Vec<Token<'a>> {
+    match language.to_ascii_lowercase().as_str() {
+        "html" | "xml" => markup::tokenize(code),
+        "css" => css::tokenize(code),
+        other => match languages::spec(other) {
+            Some(spec) => generic::tokenize(spec, code),
+            None if code.is_empty() => Vec::new(),
+            None => vec![Token { kind: TokenKind::Plain, text: code }],
+        },
+    }
+}
+
+/// Collects tokens, merging neighbours of the same kind so runs of
+/// whitespace and punctuation don't each become their own element.
+struct Tokens<'a> {
+    code: &'a str,
+    tokens: Vec<Token<'a>>,
+    /// Start of the last token in `code`, for merging.
+    last_start: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(code: &'a str) -> Self {
+        Self { code, tokens: Vec::new(), last_start: 0 }
+    }
+
+    /// Adds `code[start..end]` as a token of `kind`.
+    fn push(&mut self, kind: TokenKind, start: usize, end: usize) {
+        if start == end {
+            return;
+        }
+        match self.tokens.last_mut() {
+            Some(last) if last.kind == kind => {
+                last.text = &self.code[self.last_start..end];
+            }
+            _ => {
+                self.tokens.push(Token { kind, text: &self.code[start..end] });
+                self.last_start = start;
+            }
+        }
+    }
+
+    fn finish(self) -> Vec<Token<'a>> {
+        self.tokens
+    }
+}
+
+/// Length of the quoted string at the start of `rest`, which begins with
+/// `quote`. Backslash escapes are skipped; an unterminated string runs to the
+/// end of the line, or to the end of the code when `multiline`.
+fn quoted_len(rest: &str, quote: char, multiline: bool) -> usize {
+    let mut chars = rest.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '\n' if !multiline => return i,
+            c if c == quote => return i + c.len_utf8(),
+            _ => {}
+        }
+    }
+    rest.len()
+}
+
+/// Length of the run at the start of `rest` whose chars satisfy `pred`.
+fn run_len(rest: &str, pred: impl Fn(char) -> bool) -> usize {
+    rest.find(|c| !pred(c)).unwrap_or(rest.len())
+}
+
+/// Length of a number literal: digits, hex/binary prefixes, type suffixes
+/// and a decimal point only when a digit follows, so `0..10` stays a range.
+fn number_len(rest: &str) -> usize {
+    let bytes = rest.as_bytes();
+    let mut len = 0;
+    while len < bytes.len() {
+        let b = bytes[len];
+        let decimal_point = b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if b.is_ascii_alphanumeric() || b == b'_' || decimal_point {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_always_rebuild_the_code() {
+        let samples = [
+            "",
+            "fn main() { println!(\"héllo {}\", 'é'); }",
+            "let s = \"never closed\nlet t = 'x",
+            "/* open comment\n  still open",
+            "x = '''triple\nnot closed",
+            "trailing backslash \"\\",
+            "`template ${literal}\n",
+            "<div class=\"a\"><!-- unclosed <b",
+            "@media (max-width: 600px) { .a { color: #fff; } } }",
+            "\u{1F980} 0..10 1.5e3 0xff_u8 .5em",
+        ];
+        let languages = ["Rust", "JavaScript", "Python", "Go", "C", "Ruby", "PHP", "HTML", "CSS", "Plain"];
+        for language in languages {
+            for code in samples {
+                let tokens = highlight(language, code);
+                let rebuilt: String = tokens.iter().map(|token| token.text).collect();
+                assert_eq!(rebuilt, code, "{language} lost text from {code:?}");
+                assert!(tokens.iter().all(|token| !token.text.is_empty()), "{language}: empty token in {code:?}");
+                let unmerged = tokens.windows(2).any(|pair| pair[0].kind == pair[1].kind);
+                assert!(!unmerged, "{language}: neighbouring tokens of one kind in {code:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_languages_are_one_plain_token() {
+        assert_eq!(highlight("Brainfuck", "+[>.]"), [Token { kind: TokenKind::Plain, text: "+[>.]" }]);
+    }
+
+    #[test]
+    fn language_names_ignore_case() {
+        assert_eq!(highlight("RUST", "fn")[0].kind, TokenKind::Keyword);
+        assert_eq!(highlight("rs", "fn")[0].kind, TokenKind::Keyword);
+    }
+}
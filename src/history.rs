@@ -0,0 +1,109 @@
+//! Per-snippet revision history.
+//!
+//! Every save, from the edit form or a vault-wide tag rename, appends a
+//! [`Revision`] to the snippet, so the history always ends with the current
+//! content. Only the latest [`MAX_REVISIONS`] are kept, since the history is
+//! saved inline with the vault and a browser caps that at a few megabytes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::Snippet;
+
+/// How many revisions each snippet keeps, the current one included.
+pub const MAX_REVISIONS: usize = 50;
+
+/// The editable content of a snippet as it was saved at one point in time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    pub title: String,
+    pub language: String,
+    pub code: String,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl Revision {
+    pub fn of(snippet: &Snippet) -> Self {
+        Self {
+            title: snippet.title.clone(),
+            language: snippet.language.clone(),
+            code: snippet.code.clone(),
+            description: snippet.description.clone(),
+            tags: snippet.tags.clone(),
+            saved_at: snippet.updated_at,
+        }
+    }
+
+    /// Same content, ignoring when it was saved.
+    fn same_content(&self, other: &Revision) -> bool {
+        Revision { saved_at: other.saved_at, ..self.clone() } == *other
+    }
+}
+
+/// Records `saved` in its own history.
+///
+/// Snippets last saved before history existed first get their `previous`
+/// content recorded, so there is something to compare the edit against.
+/// Saving without changing anything adds no revision, and past
+/// [`MAX_REVISIONS`] the oldest ones are dropped.
+pub fn record_save(previous: Option<&Snippet>, saved: &mut Snippet) {
+    if let Some(previous) = previous.filter(|_| saved.history.is_empty()) {
+        saved.history.push(Revision::of(previous));
+    }
+    let revision = Revision::of(saved);
+    if !saved.history.last().is_some_and(|last| last.same_content(&revision)) {
+        saved.history.push(revision);
+    }
+    let excess = saved.history.len().saturating_sub(MAX_REVISIONS);
+    saved.history.drain(..excess);
+}
+
+/// Puts `revision`'s content back on `snippet` as a new save at `now`.
+pub fn restore(snippet: &mut Snippet, revision: &Revision, now: DateTime<Utc>) {
+    let previous = snippet.clone();
+    snippet.title = revision.title.clone();
+    snippet.language = revision.language.clone();
+    snippet.code = revision.code.clone();
+    snippet.description = revision.description.clone();
+    snippet.tags = revision.tags.clone();
+    snippet.updated_at = now;
+    record_save(Some(&previous), snippet);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edited(snippet: &Snippet, code: &str) -> Snippet {
+        let mut saved = Snippet { code: code.to_string(), ..snippet.clone() };
+        record_save(Some(snippet), &mut saved);
+        saved
+    }
+
+    #[test]
+    fn first_save_records_the_previous_content_too() {
+        let original = Snippet::for_test("1", "Hello", "println!(\"hi\");");
+        let saved = edited(&original, "println!(\"hello\");");
+        let codes: Vec<&str> = saved.history.iter().map(|r| r.code.as_str()).collect();
+        assert_eq!(codes, ["println!(\"hi\");", "println!(\"hello\");"]);
+    }
+
+    #[test]
+    fn unchanged_save_adds_nothing() {
+        let saved = edited(&Snippet::for_test("1", "Hello", "a"), "b");
+        assert_eq!(edited(&saved, "b").history.len(), 2);
+    }
+
+    #[test]
+    fn keeps_only_the_latest_revisions() {
+        let mut snippet = Snippet::for_test("1", "Counter", "0");
+        for n in 1..=MAX_REVISIONS + 10 {
+            snippet = edited(&snippet, &n.to_string());
+        }
+        assert_eq!(snippet.history.len(), MAX_REVISIONS);
+        assert_eq!(snippet.history.last().unwrap().code, snippet.code);
+        assert_eq!(snippet.history[0].code, "11");
+    }
+}
@@ -1,8 +1,31 @@
 #![allow(non_snake_case)]
+use chrono::{DateTime, TimeZone, Utc};
+use dioxus::logger::tracing;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+mod ai;
+mod clipboard;
+mod collections;
 mod components;
+mod diff;
+mod highlight;
+mod history;
+mod preview;
+mod routes;
+mod search;
+mod settings;
+mod store;
+mod tags;
+mod time;
+
+use ai::CachedExplanation;
+use collections::Collection;
+use components::toast::Toasts;
+use history::Revision;
+use routes::Route;
+use settings::Settings;
+use store::{SnippetStore, StoreError};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Snippet {
@@ -12,160 +35,144 @@ pub struct Snippet {
     pub code: String,
     pub description: Option<String>,
     pub tags: Vec<String>,
-    pub created_at: String,
-    pub updated_at: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
     pub is_favorite: bool,
+    /// The last AI explanation; kept when the code changes, but shown as stale.
+    #[serde(default)]
+    pub explanation: Option<CachedExplanation>,
+    /// When the snippet was moved to the trash; `None` while it is live.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Every saved version, oldest first.
+    #[serde(default)]
+    pub history: Vec<Revision>,
+    /// Id of the collection the snippet is filed in, if any.
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// How often the snippet was opened and copied, for sorting by use.
+    #[serde(default)]
+    pub view_count: u32,
+    #[serde(default)]
+    pub copy_count: u32,
+}
+
+impl Snippet {
+    /// A fresh random (UUID v4) id, safe to generate in bulk on any platform.
+    ///
+    /// Snippets saved before this used millisecond timestamps as ids; those
+    /// are kept as they are so existing links keep working.
+    pub fn new_id() -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum Screen {
-    Home,
-    Add,
-    Edit(String),
-    View(String),
+#[cfg(test)]
+impl Snippet {
+    /// A plain Rust snippet with nothing but an id, title and code.
+    pub fn for_test(id: &str, title: &str, code: &str) -> Self {
+        Snippet {
+            id: id.to_string(),
+            title: title.to_string(),
+            language: "Rust".to_string(),
+            code: code.to_string(),
+            description: None,
+            tags: Vec::new(),
+            created_at: sample_date(1),
+            updated_at: sample_date(1),
+            is_favorite: false,
+            explanation: None,
+            deleted_at: None,
+            history: Vec::new(),
+            collection: None,
+            view_count: 0,
+            copy_count: 0,
+        }
+    }
 }
 
 fn main() {
     launch(App);
 }
 
+/// Shared state every route reads from context.
+#[derive(Clone, Copy)]
+pub struct AppState {
+    pub store: Signal<Box<dyn SnippetStore>>,
+    /// Why the saved vault could not be opened, if it couldn't.
+    pub load_error: Signal<Option<StoreError>>,
+    /// Why the saved collections and settings could not be read, if they
+    /// couldn't; they are not written back while this is set.
+    pub collections_error: Signal<Option<StoreError>>,
+    pub settings_error: Signal<Option<StoreError>>,
+    pub search_query: Signal<String>,
+    pub selected_language: Signal<String>,
+    /// The collection the home screen is scoped to; `None` shows everything.
+    pub selected_collection: Signal<Option<String>>,
+    pub collections: Signal<Vec<Collection>>,
+    /// The snippet the edit form just saved; reopening its detail page
+    /// afterwards isn't counted as a view.
+    pub just_saved: Signal<Option<String>>,
+    pub toasts: Toasts,
+    pub settings: Signal<Settings>,
+}
+
 #[component]
 fn App() -> Element {
-    let mut snippets = use_signal(|| get_sample_snippets());
-    let mut current_screen = use_signal(|| Screen::Home);
-    let mut search_query = use_signal(|| String::new());
-    let mut selected_language = use_signal(|| String::new());
+    use_context_provider(|| {
+        // Sample data is only used on first run, before anything has been saved.
+        let (mut store, error) = store::open_default(get_sample_snippets);
+        let (settings, settings_error) = match settings::load() {
+            Ok(settings) => (settings, None),
+            Err(err) => (Settings::default(), Some(err)),
+        };
+        let (collections, collections_error) = match collections::load() {
+            Ok(collections) => (collections, None),
+            Err(err) => (Vec::new(), Some(err)),
+        };
+        let toasts = Toasts::new();
+        // The default retention may be shorter than the one that didn't load.
+        if let Some(cutoff) = settings.trash_cutoff(time::now()).filter(|_| settings_error.is_none()) {
+            report(toasts, store.purge_trashed_before(cutoff));
+        }
+        AppState {
+            store: Signal::new(store),
+            load_error: Signal::new(error),
+            collections_error: Signal::new(collections_error),
+            settings_error: Signal::new(settings_error),
+            search_query: Signal::new(String::new()),
+            selected_language: Signal::new(String::new()),
+            selected_collection: Signal::new(None),
+            collections: Signal::new(collections),
+            just_saved: Signal::new(None),
+            toasts,
+            settings: Signal::new(settings),
+        }
+    });
 
     rsx! {
         style { {include_str!("../assets/styles.css")} }
-        div { class: "app-container",
-            // Header
-            header { class: "app-header",
-                div { class: "header-content",
-                    div { class: "header-left",
-                        h1 { class: "app-title",
-                            span { class: "app-icon", "📱" }
-                            "Code Vault"
-                        }
-                    }
-                    div { class: "header-right",
-                        if matches!(current_screen(), Screen::Home) {
-                            button { 
-                                class: "btn btn-primary btn-sm",
-                                onclick: move |_| current_screen.set(Screen::Add),
-                                span { class: "btn-icon", "+" }
-                                "Add"
-                            }
-                        }
-                        if !matches!(current_screen(), Screen::Home) {
-                            button { 
-                                class: "btn btn-ghost btn-sm",
-                                onclick: move |_| current_screen.set(Screen::Home),
-                                span { class: "btn-icon", "←" }
-                                "Back"
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Main Content
-            main { class: "main-content",
-                match current_screen() {
-                    Screen::Home => rsx! {
-                        components::home_screen::HomeScreen {
-                            snippets: snippets(),
-                            search_query: search_query(),
-                            selected_language: selected_language(),
-                            on_search: move |query: String| search_query.set(query),
-                            on_language_filter: move |lang: String| selected_language.set(lang),
-                            on_view_snippet: move |id: String| current_screen.set(Screen::View(id)),
-                            on_edit_snippet: move |id: String| current_screen.set(Screen::Edit(id)),
-                            on_delete_snippet: move |id: String| {
-                                snippets.write().retain(|s| s.id != id);
-                            },
-                            on_toggle_favorite: move |id: String| {
-                                if let Some(snippet) = snippets.write().iter_mut().find(|s| s.id == id) {
-                                    snippet.is_favorite = !snippet.is_favorite;
-                                }
-                            }
-                        }
-                    },
-                    Screen::Add => rsx! {
-                        components::add_edit_snippet::AddEditScreen {
-                            snippet: None,
-                            on_save: move |snippet: Snippet| {
-                                snippets.write().push(snippet);
-                                current_screen.set(Screen::Home);
-                            },
-                            on_cancel: move |_| current_screen.set(Screen::Home)
-                        }
-                    },
-                    Screen::Edit(id) => {
-                        let snippet = snippets().iter().find(|s| s.id == id).cloned();
-                        rsx! {
-                            components::add_edit_snippet::AddEditScreen {
-                                snippet,
-                                on_save: move |updated_snippet: Snippet| {
-                                    let snippet_id = updated_snippet.id.clone();
-                                    if let Some(snippet) = snippets.write().iter_mut().find(|s| s.id == updated_snippet.id) {
-                                        *snippet = updated_snippet;
-                                    }
-                                    current_screen.set(Screen::View(snippet_id));
-                                },
-                                on_cancel: move |_| current_screen.set(Screen::Home)
-                            }
-                        }
-                    },
-                    Screen::View(id) => {
-                        let snippet = snippets().iter().find(|s| s.id == id).cloned();
-                        rsx! {
-                            components::snippet_detail::SnippetDetail {
-                                snippet,
-                                on_edit: move |id: String| current_screen.set(Screen::Edit(id)),
-                                on_delete: move |id: String| {
-                                    snippets.write().retain(|s| s.id != id);
-                                    current_screen.set(Screen::Home);
-                                },
-                                on_toggle_favorite: move |id: String| {
-                                    if let Some(snippet) = snippets.write().iter_mut().find(|s| s.id == id) {
-                                        snippet.is_favorite = !snippet.is_favorite;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        Router::<Route> {}
+    }
+}
 
-            // Bottom Navigation (Mobile)
-            nav { class: "bottom-nav",
-                button {
-                    class: if matches!(current_screen(), Screen::Home) { "nav-btn active" } else { "nav-btn" },
-                    onclick: move |_| current_screen.set(Screen::Home),
-                    span { class: "nav-icon", "🏠" }
-                    span { class: "nav-label", "Home" }
-                }
-                button {
-                    class: "nav-btn",
-                    onclick: move |_| {
-                        // Future search functionality
-                        web_sys::console::log_1(&"Search clicked".into());
-                    },
-                    span { class: "nav-icon", "🔍" }
-                    span { class: "nav-label", "Search" }
-                }
-                button {
-                    class: if matches!(current_screen(), Screen::Add) { "nav-btn active" } else { "nav-btn" },
-                    onclick: move |_| current_screen.set(Screen::Add),
-                    span { class: "nav-icon", "+" }
-                    span { class: "nav-label", "Add" }
-                }
-            }
-        }
+/// Logs a failed store operation and tells the user in a toast; the UI
+/// keeps showing the in-memory state.
+fn report<T>(mut toasts: Toasts, result: Result<T, StoreError>) {
+    if let Err(err) = result {
+        tracing::error!("{err}");
+        toasts.error(format!("Could not save: {err}"));
     }
 }
 
+fn sample_date(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap()
+}
+
 fn get_sample_snippets() -> Vec<Snippet> {
     vec![
         Snippet {
@@ -175,9 +182,15 @@ fn get_sample_snippets() -> Vec<Snippet> {
             code: "fn main() {\n    println!(\"Hello, world!\");\n}".into(),
             description: Some("A simple Hello World program in Rust".into()),
             tags: vec!["beginner".into(), "rust".into(), "hello-world".into()],
-            created_at: "2024-01-15".into(),
-            updated_at: "2024-01-15".into(),
+            created_at: sample_date(15),
+            updated_at: sample_date(15),
             is_favorite: true,
+            explanation: None,
+            deleted_at: None,
+            history: Vec::new(),
+            collection: None,
+            view_count: 0,
+            copy_count: 0,
         },
         Snippet {
             id: "2".into(),
@@ -186,9 +199,15 @@ fn get_sample_snippets() -> Vec<Snippet> {
             code: "for (let i = 0; i < 10; i++) {\n    console.log(`Count: ${i}`);\n}".into(),
             description: Some("Basic for loop example in JavaScript".into()),
             tags: vec!["javascript".into(), "loop".into(), "basics".into()],
-            created_at: "2024-01-14".into(),
-            updated_at: "2024-01-14".into(),
+            created_at: sample_date(14),
+            updated_at: sample_date(14),
             is_favorite: false,
+            explanation: None,
+            deleted_at: None,
+            history: Vec::new(),
+            collection: None,
+            view_count: 0,
+            copy_count: 0,
         },
         Snippet {
             id: "3".into(),
@@ -197,9 +216,15 @@ fn get_sample_snippets() -> Vec<Snippet> {
             code: "squares = [x**2 for x in range(10)]\nprint(squares)".into(),
             description: Some("Creating a list of squares using list comprehension".into()),
             tags: vec!["python".into(), "list-comprehension".into(), "functional".into()],
-            created_at: "2024-01-13".into(),
-            updated_at: "2024-01-13".into(),
+            created_at: sample_date(13),
+            updated_at: sample_date(13),
             is_favorite: true,
+            explanation: None,
+            deleted_at: None,
+            history: Vec::new(),
+            collection: None,
+            view_count: 0,
+            copy_count: 0,
         },
     ]
 }
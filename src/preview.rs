@@ -0,0 +1,82 @@
+//! Short previews of snippet code for cards.
+
+/// The start of a snippet's code, cut to fit on a card.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodePreview {
+    pub text: String,
+    /// Whole lines left out after the preview.
+    pub hidden_lines: usize,
+}
+
+/// Takes at most `max_lines` lines and `max_chars` characters of `code`.
+///
+/// Counting is by `char`, never by byte, so multi-byte text (emoji, CJK,
+/// accented identifiers) can't be split mid-character. Indentation is kept
+/// as is; a line cut short by the character budget ends in `…`.
+pub fn code_preview(code: &str, max_lines: usize, max_chars: usize) -> CodePreview {
+    let total_lines = code.lines().count();
+    let mut text = String::new();
+    let mut budget = max_chars;
+    let mut shown_lines = 0;
+
+    for line in code.lines().take(max_lines) {
+        if shown_lines > 0 {
+            text.push('\n');
+        }
+        shown_lines += 1;
+
+        let line_chars = line.chars().count();
+        if line_chars > budget {
+            text.extend(line.chars().take(budget));
+            text.push('…');
+            break;
+        }
+        text.push_str(line);
+        budget -= line_chars;
+    }
+
+    CodePreview {
+        text,
+        hidden_lines: total_lines - shown_lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emoji_across_the_100th_byte_stays_whole() {
+        // The crab takes bytes 98..102, so a byte cut at 100 would split it.
+        let code = format!("{}🦀 and more", "a".repeat(98));
+        let preview = code_preview(&code, 5, 100);
+        assert_eq!(preview.text, format!("{}🦀 …", "a".repeat(98)));
+        assert_eq!(preview.text.chars().count(), 101);
+    }
+
+    #[test]
+    fn cjk_text_counts_characters_not_bytes() {
+        // 40 three-byte characters: 120 bytes, but within a 50 character budget.
+        let code = "漢".repeat(40);
+        assert_eq!(code_preview(&code, 5, 50).text, code);
+
+        let cut = code_preview(&code, 5, 34);
+        assert_eq!(cut.text, format!("{}…", "漢".repeat(34)));
+    }
+
+    #[test]
+    fn budget_runs_out_on_a_later_line() {
+        let code = "// コメント\nlet 名前 = \"値\";\nprintln!(\"{名前}\");";
+        let preview = code_preview(code, 5, 15);
+        assert_eq!(preview.text, "// コメント\nlet 名前 =…");
+        assert_eq!(preview.hidden_lines, 1);
+    }
+
+    #[test]
+    fn counts_the_lines_left_out() {
+        let code = "one\ntwo\nthree\nfour\nfive";
+        assert_eq!(code_preview(code, 2, 100), CodePreview { text: "one\ntwo".into(), hidden_lines: 3 });
+        assert_eq!(code_preview(code, 10, 100).hidden_lines, 0);
+        assert_eq!(code_preview("", 3, 100), CodePreview { text: String::new(), hidden_lines: 0 });
+    }
+}
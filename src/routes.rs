@@ -0,0 +1,515 @@
+//! URL routes. Every screen has its own address so the back button, refresh
+//! and shared links land where the user expects.
+
+#![allow(non_snake_case)]
+use dioxus::prelude::*;
+
+use crate::components::add_edit_snippet::AddEditScreen;
+use crate::components::home_screen::HomeScreen;
+use crate::components::search_screen::SearchScreen;
+use crate::components::settings_screen::SettingsScreen;
+use crate::components::snippet_detail::SnippetDetail;
+use crate::components::tag_manager::TagManagerScreen;
+use crate::components::trash_screen::TrashScreen;
+use crate::components::toast::ToastHost;
+use crate::ai::CachedExplanation;
+use crate::collections::{self, Collection};
+use crate::store::{StoreError, Usage};
+use crate::{clipboard, history, report, settings, tags, time, AppState, Snippet};
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+pub enum Route {
+    #[layout(AppShell)]
+        #[route("/?:sort")]
+        Home { sort: String },
+        #[route("/new")]
+        NewSnippet {},
+        #[route("/s/:id")]
+        ViewSnippet { id: String },
+        #[route("/s/:id/edit")]
+        EditSnippet { id: String },
+        #[route("/search?:q")]
+        Search { q: String },
+        #[route("/trash")]
+        Trash {},
+        #[route("/tags")]
+        Tags {},
+        #[route("/settings")]
+        Settings {},
+}
+
+/// Sends a message when `/` is pressed outside a text field. On the search
+/// screen it focuses the search box instead, keeping the current query.
+const SEARCH_SHORTCUT: &str = r#"
+    document.addEventListener("keydown", (event) => {
+        if (event.key !== "/" || event.ctrlKey || event.metaKey || event.altKey) return;
+        const target = event.target;
+        if (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.tagName)) return;
+        event.preventDefault();
+        const input = document.querySelector(".search-screen .search-input");
+        if (input) {
+            input.focus();
+        } else {
+            dioxus.send(null);
+        }
+    });
+"#;
+
+/// Header, bottom navigation and the load error banner around every screen.
+#[component]
+fn AppShell() -> Element {
+    let state = use_context::<AppState>();
+    let route = use_route::<Route>();
+    let is_home = matches!(route, Route::Home { .. });
+    let load_errors: Vec<(&str, StoreError)> = [
+        ("snippets", state.load_error),
+        ("collections", state.collections_error),
+        ("settings", state.settings_error),
+    ]
+    .into_iter()
+    .filter_map(|(what, error)| Some((what, error()?)))
+    .collect();
+
+    use_future(|| async {
+        let navigator = navigator();
+        let mut shortcut = document::eval(SEARCH_SHORTCUT);
+        while shortcut.recv::<()>().await.is_ok() {
+            navigator.push(Route::Search { q: String::new() });
+        }
+    });
+
+    rsx! {
+        div { class: "app-container",
+            // Header
+            header { class: "app-header",
+                div { class: "header-content",
+                    div { class: "header-left",
+                        h1 { class: "app-title",
+                            span { class: "app-icon", "📱" }
+                            "Code Vault"
+                        }
+                    }
+                    div { class: "header-right",
+                        Link {
+                            class: "btn btn-ghost btn-sm",
+                            to: Route::Tags {},
+                            title: "Tags",
+                            "🏷️"
+                        }
+                        Link {
+                            class: "btn btn-ghost btn-sm",
+                            to: Route::Settings {},
+                            title: "Settings",
+                            "⚙️"
+                        }
+                        if is_home {
+                            Link {
+                                class: "btn btn-primary btn-sm",
+                                to: Route::NewSnippet {},
+                                span { class: "btn-icon", "+" }
+                                "Add"
+                            }
+                        } else {
+                            Link {
+                                class: "btn btn-ghost btn-sm",
+                                to: Route::Home { sort: String::new() },
+                                span { class: "btn-icon", "←" }
+                                "Back"
+                            }
+                        }
+                    }
+                }
+            }
+
+            for (what, err) in load_errors {
+                div { class: "load-error",
+                    strong { "Your saved {what} could not be loaded." }
+                    p { "{err}" }
+                    p { "The saved data has been left untouched, but changes made now will not be saved." }
+                }
+            }
+
+            // Main Content
+            main { class: "main-content",
+                Outlet::<Route> {}
+            }
+
+            ToastHost {}
+
+            // Bottom Navigation (Mobile)
+            nav { class: "bottom-nav",
+                Link {
+                    class: if is_home { "nav-btn active" } else { "nav-btn" },
+                    to: Route::Home { sort: String::new() },
+                    span { class: "nav-icon", "🏠" }
+                    span { class: "nav-label", "Home" }
+                }
+                Link {
+                    class: if matches!(route, Route::Search { .. }) { "nav-btn active" } else { "nav-btn" },
+                    to: Route::Search { q: String::new() },
+                    span { class: "nav-icon", "🔍" }
+                    span { class: "nav-label", "Search" }
+                }
+                Link {
+                    class: if matches!(route, Route::NewSnippet {}) { "nav-btn active" } else { "nav-btn" },
+                    to: Route::NewSnippet {},
+                    span { class: "nav-icon", "+" }
+                    span { class: "nav-label", "Add" }
+                }
+                Link {
+                    class: if matches!(route, Route::Trash {}) { "nav-btn active" } else { "nav-btn" },
+                    to: Route::Trash {},
+                    span { class: "nav-icon", "🗑️" }
+                    span { class: "nav-label", "Trash" }
+                }
+            }
+        }
+    }
+}
+
+/// The vault, in the order named by `?sort=` or else the saved preference.
+#[component]
+fn Home(sort: String) -> Element {
+    let state = use_context::<AppState>();
+    let AppState {
+        mut store,
+        mut search_query,
+        mut selected_language,
+        mut selected_collection,
+        mut collections,
+        settings,
+        toasts,
+        ..
+    } = state;
+
+    rsx! {
+        HomeScreen {
+            search_query: search_query(),
+            selected_language: selected_language(),
+            on_search: move |query: String| search_query.set(query),
+            on_language_filter: move |lang: String| selected_language.set(lang),
+            on_view_snippet: move |id: String| { navigator().push(Route::ViewSnippet { id }); },
+            on_edit_snippet: move |id: String| { navigator().push(Route::EditSnippet { id }); },
+            on_delete_snippet: move |id: String| trash_snippet(state, &id),
+            on_toggle_favorite: move |id: String| report(toasts, store.write().toggle_favorite(&id)),
+            on_copy_snippet: move |id: String| copy_snippet(state, &id),
+            saved_searches: settings.read().saved_searches.clone(),
+            on_save_search: move |saved: settings::SavedSearch| {
+                let mut updated = settings();
+                updated.save_search(saved);
+                save_settings(state, updated);
+            },
+            on_remove_saved_search: move |name: String| {
+                let mut updated = settings();
+                updated.saved_searches.retain(|saved| saved.name != name);
+                save_settings(state, updated);
+            },
+            collections: collections(),
+            selected_collection: selected_collection(),
+            on_select_collection: move |id: Option<String>| selected_collection.set(id),
+            on_create_collection: move |(name, parent): (String, Option<String>)| {
+                collections.write().push(Collection { id: Snippet::new_id(), name, parent });
+                save_collections(state);
+            },
+            on_delete_collection: move |id: String| {
+                let moved = collections::remove(&mut collections.write(), store.read().list(), &id);
+                for snippet in moved {
+                    report(toasts, store.write().update(snippet));
+                }
+                save_collections(state);
+                if selected_collection().is_some_and(|selected| !collections.read().iter().any(|c| c.id == selected)) {
+                    selected_collection.set(None);
+                }
+            },
+            on_move_snippet: move |(id, collection): (String, Option<String>)| {
+                let Some(mut snippet) = store.read().get(&id).cloned() else {
+                    return;
+                };
+                snippet.collection = collection;
+                report(toasts, store.write().update(snippet));
+            },
+            sort_order: settings::SortOrder::from_key(&sort).unwrap_or(settings.read().sort_order),
+            on_sort: move |order: settings::SortOrder| {
+                let mut updated = settings();
+                updated.sort_order = order;
+                save_settings(state, updated);
+                navigator().replace(Route::Home { sort: order.key().to_string() });
+            },
+        }
+    }
+}
+
+/// Search results for the query in the URL, so a search can be shared as a link.
+#[component]
+fn Search(q: String) -> Element {
+    let state = use_context::<AppState>();
+    let settings = state.settings;
+
+    rsx! {
+        SearchScreen {
+            query: q.clone(),
+            recent_searches: settings.read().recent_searches.clone(),
+            on_query: move |q: String| { navigator().replace(Route::Search { q }); },
+            on_open: move |id: String| {
+                let mut updated = settings();
+                updated.remember_search(&q);
+                save_settings(state, updated);
+                navigator().push(Route::ViewSnippet { id });
+            },
+            on_clear_recent: move |_| {
+                let mut updated = settings();
+                updated.recent_searches.clear();
+                save_settings(state, updated);
+            },
+        }
+    }
+}
+
+#[component]
+fn NewSnippet() -> Element {
+    let AppState { mut store, selected_collection, toasts, .. } = use_context();
+
+    rsx! {
+        AddEditScreen {
+            snippet: None,
+            on_save: move |mut snippet: Snippet| {
+                // Adding while browsing a collection files the snippet there.
+                snippet.collection = selected_collection();
+                report(toasts, store.write().insert(snippet));
+                navigator().push(Route::Home { sort: String::new() });
+            },
+            on_cancel: move |_| { navigator().push(Route::Home { sort: String::new() }); },
+        }
+    }
+}
+
+#[component]
+fn EditSnippet(id: String) -> Element {
+    let AppState { mut store, mut just_saved, toasts, .. } = use_context();
+    let snippet = store.read().get(&id).cloned();
+
+    rsx! {
+        AddEditScreen {
+            snippet,
+            on_save: move |updated_snippet: Snippet| {
+                let id = updated_snippet.id.clone();
+                report(toasts, store.write().update(updated_snippet));
+                just_saved.set(Some(id.clone()));
+                navigator().push(Route::ViewSnippet { id });
+            },
+            on_cancel: move |_| { navigator().push(Route::Home { sort: String::new() }); },
+        }
+    }
+}
+
+#[component]
+fn ViewSnippet(id: String) -> Element {
+    let state = use_context::<AppState>();
+    let AppState { mut store, mut toasts, mut just_saved, .. } = state;
+    // Keyed on the id, since going from one snippet to another keeps this
+    // component mounted.
+    use_effect(use_reactive!(|id| {
+        if just_saved.peek().as_deref() == Some(id.as_str()) {
+            just_saved.set(None);
+        } else {
+            count_use(state, &id, Usage::View);
+        }
+    }));
+    let snippet = store.read().get(&id).cloned();
+
+    rsx! {
+        SnippetDetail {
+            snippet,
+            on_edit: move |id: String| { navigator().push(Route::EditSnippet { id }); },
+            on_delete: move |id: String| {
+                trash_snippet(state, &id);
+                navigator().push(Route::Home { sort: String::new() });
+            },
+            on_toggle_favorite: move |id: String| report(toasts, store.write().toggle_favorite(&id)),
+            on_copy: move |id: String| copy_snippet(state, &id),
+            on_explained: {
+                let id = id.clone();
+                move |explanation: CachedExplanation| {
+                    let Some(mut snippet) = store.read().get(&id).cloned() else {
+                        return;
+                    };
+                    snippet.explanation = Some(explanation);
+                    report(toasts, store.write().update(snippet));
+                }
+            },
+            on_restore_revision: move |(id, index): (String, usize)| {
+                let Some(mut snippet) = store.read().get(&id).cloned() else {
+                    return;
+                };
+                let Some(revision) = snippet.history.get(index).cloned() else {
+                    return;
+                };
+                history::restore(&mut snippet, &revision, time::now());
+                report(toasts, store.write().update(snippet));
+                toasts.success(format!("Restored v{}", index + 1));
+            },
+        }
+    }
+}
+
+#[component]
+fn Trash() -> Element {
+    let AppState { mut store, settings, mut toasts, .. } = use_context();
+
+    // Purge anything that expired while the app was open.
+    use_hook(move || {
+        if let Some(cutoff) = settings.peek().trash_cutoff(time::now()) {
+            report(toasts, store.write().purge_trashed_before(cutoff));
+        }
+    });
+
+    let trashed: Vec<Snippet> = store.read().list().iter().filter(|s| s.is_trashed()).cloned().collect();
+
+    rsx! {
+        TrashScreen {
+            snippets: trashed,
+            retention_days: settings.read().trash_retention_days,
+            on_restore: move |ids: Vec<String>| {
+                for id in &ids {
+                    report(toasts, store.write().restore(id));
+                }
+                let plural = if ids.len() == 1 { "" } else { "s" };
+                toasts.success(format!("Restored {} snippet{plural}", ids.len()));
+            },
+            on_delete_forever: move |ids: Vec<String>| {
+                for id in &ids {
+                    report(toasts, store.write().delete(id));
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn Tags() -> Element {
+    let state = use_context::<AppState>();
+    let AppState { store, settings, mut toasts, .. } = state;
+    let usage = tags::usage(store.read().list());
+
+    rsx! {
+        TagManagerScreen {
+            tags: usage,
+            rules: settings.read().tag_rules.clone(),
+            on_rename: move |(from, to): (String, String)| {
+                let changed = retag(state, std::slice::from_ref(&from), Some(&to));
+                toasts.success(format!("Renamed #{from} to #{to} on {}", snippet_count(changed)));
+            },
+            on_merge: move |(from, to): (Vec<String>, String)| {
+                let changed = retag(state, &from, Some(&to));
+                toasts.success(format!("Merged {} tags into #{to} on {}", from.len(), snippet_count(changed)));
+            },
+            on_delete: move |tag: String| {
+                let changed = retag(state, std::slice::from_ref(&tag), None);
+                toasts.success(format!("Removed #{tag} from {}", snippet_count(changed)));
+            },
+        }
+    }
+}
+
+/// Applies [`tags::retag`] to the whole vault and returns how many snippets changed.
+fn retag(state: AppState, from: &[String], to: Option<&str>) -> usize {
+    let AppState { mut store, toasts, .. } = state;
+    let changed = tags::retag(store.read().list(), from, to, time::now());
+    let count = changed.len();
+    for snippet in changed {
+        report(toasts, store.write().update(snippet));
+    }
+    count
+}
+
+fn snippet_count(count: usize) -> String {
+    if count == 1 { "1 snippet".into() } else { format!("{count} snippets") }
+}
+
+#[component]
+fn Settings() -> Element {
+    let state = use_context::<AppState>();
+    let AppState { settings, mut toasts, .. } = state;
+
+    rsx! {
+        SettingsScreen {
+            settings: settings(),
+            on_save: move |updated: settings::Settings| {
+                if save_settings(state, updated) {
+                    toasts.success("Settings saved");
+                } else {
+                    toasts.error("Settings apply to this session only, since the saved ones could not be loaded");
+                }
+            },
+        }
+    }
+}
+
+/// Saves `updated` as the settings and returns whether it was written.
+///
+/// Nothing is written while the saved settings failed to load, since that
+/// would replace them with the defaults they were swapped for.
+fn save_settings(state: AppState, updated: settings::Settings) -> bool {
+    let AppState { mut settings, settings_error, .. } = state;
+    let writable = settings_error.peek().is_none();
+    if writable {
+        settings::save(&updated);
+    }
+    settings.set(updated);
+    writable
+}
+
+/// Saves the collections, unless they failed to load, as for [`save_settings`].
+fn save_collections(state: AppState) {
+    if state.collections_error.peek().is_none() {
+        collections::save(&state.collections.peek());
+    }
+}
+
+/// Moves a snippet to the trash, offering to undo it from a toast.
+fn trash_snippet(state: AppState, id: &str) {
+    let AppState { mut store, mut toasts, .. } = state;
+    let result = store.write().trash(id);
+    match result {
+        Ok(()) => {
+            let id = id.to_string();
+            toasts.undoable("Moved to trash", move || {
+                let mut store = store;
+                report(toasts, store.write().restore(&id));
+            });
+        }
+        Err(err) => toasts.error(err.to_string()),
+    }
+}
+
+/// Copies a snippet's code and reports the outcome in a toast.
+fn copy_snippet(state: AppState, id: &str) {
+    let Some(code) = state.store.read().get(id).map(|s| s.code.clone()) else {
+        return;
+    };
+    let mut toasts = state.toasts;
+    let id = id.to_string();
+    spawn(async move {
+        match clipboard::write_text(code).await {
+            Ok(()) => {
+                toasts.success("Copied to clipboard");
+                count_use(state, &id, Usage::Copy);
+            }
+            Err(err) => toasts.error(format!("Could not copy: {err}")),
+        }
+    });
+}
+
+/// Bumps one of a snippet's use counters, which the "most viewed" and
+/// "most copied" sort orders go by.
+///
+/// Only writes to the store, so an effect counting a view doesn't rerun on
+/// the change it makes. A snippet that has gone away has nothing to count.
+fn count_use(state: AppState, id: &str, usage: Usage) {
+    let AppState { mut store, toasts, .. } = state;
+    let result = store.write().record_use(id, usage);
+    if !matches!(result, Err(StoreError::NotFound(_))) {
+        report(toasts, result);
+    }
+}
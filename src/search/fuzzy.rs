@@ -0,0 +1,96 @@
+//! Typo-tolerant, ranked matching for the free-text words of a query.
+//!
+//! Every word of the query must turn up somewhere in the snippet. How well
+//! it matched (exact word, prefix, substring, abbreviation, typo) times the
+//! weight of the field it matched in gives the word's score, and a snippet's
+//! relevance is the sum over its words, so `lst cmprh` still finds
+//! "Python List Comprehension".
+
+use crate::Snippet;
+
+use super::{find, matches};
+use super::words::{quality, words, Quality};
+
+/// How much a match in each field counts; a hit in the title outranks the
+/// same hit in the code.
+const TITLE_WEIGHT: u32 = 8;
+const TAG_WEIGHT: u32 = 4;
+const DESCRIPTION_WEIGHT: u32 = 2;
+const CODE_WEIGHT: u32 = 1;
+
+/// The snippet's relevance to `text`, or `None` if some word of it is missing.
+///
+/// Text with no words in it, like `=>` or `::`, has nothing to match fuzzily
+/// and is looked for verbatim instead.
+pub fn score(snippet: &Snippet, text: &str) -> Option<u32> {
+    let query_words = words(text);
+    if query_words.is_empty() {
+        return matches(snippet, text).then_some(Quality::Substring.weighted(CODE_WEIGHT));
+    }
+    let title_words = words(&snippet.title);
+    let tag_words: Vec<String> = snippet.tags.iter().flat_map(|tag| words(tag)).collect();
+    let description_words = snippet.description.as_deref().map(words).unwrap_or_default();
+
+    let mut total = 0;
+    for word in &query_words {
+        let best = [
+            best_quality(word, &title_words, true).map(|q| q.weighted(TITLE_WEIGHT)),
+            best_quality(word, &tag_words, false).map(|q| q.weighted(TAG_WEIGHT)),
+            best_quality(word, &description_words, false).map(|q| q.weighted(DESCRIPTION_WEIGHT)),
+            find(&snippet.code, word).map(|_| Quality::Substring.weighted(CODE_WEIGHT)),
+        ];
+        total += best.into_iter().flatten().max()?;
+    }
+
+    // The words appearing together, in order, in the title beats them
+    // being scattered around the snippet.
+    if query_words.len() > 1 && find(&snippet.title, text.trim()).is_some() {
+        total += Quality::Exact.weighted(TITLE_WEIGHT);
+    }
+    Some(total)
+}
+
+/// The relevance of a quoted phrase, which only ever matches verbatim.
+pub fn phrase_score(snippet: &Snippet, phrase: &str) -> Option<u32> {
+    let in_tags = snippet.tags.iter().any(|tag| find(tag, phrase).is_some());
+    let in_description = snippet.description.as_deref().is_some_and(|d| find(d, phrase).is_some());
+    [
+        find(&snippet.title, phrase).map(|_| TITLE_WEIGHT),
+        in_tags.then_some(TAG_WEIGHT),
+        in_description.then_some(DESCRIPTION_WEIGHT),
+        find(&snippet.code, phrase).map(|_| CODE_WEIGHT),
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .map(|weight| Quality::Substring.weighted(weight))
+}
+
+fn best_quality(word: &str, candidates: &[String], abbreviations: bool) -> Option<Quality> {
+    candidates
+        .iter()
+        .filter_map(|candidate| quality(word, candidate, abbreviations))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuation_only_text_matches_verbatim() {
+        let closure = Snippet::for_test("1", "Closures", "let add = |a, b| a + b;\nmatch x { _ => 1 }");
+        let plain = Snippet::for_test("2", "Plain", "let x = 1;");
+        assert!(score(&closure, "=>").is_some());
+        assert_eq!(score(&plain, "=>"), None);
+        assert_eq!(score(&plain, "::"), None);
+    }
+
+    #[test]
+    fn abbreviations_and_typos_still_match() {
+        let snippet = Snippet::for_test("1", "Python List Comprehension", "[x for x in xs]");
+        assert!(score(&snippet, "lst cmprh").is_some());
+        assert!(score(&snippet, "comprehenison").is_some());
+        assert_eq!(score(&snippet, "dictionary"), None);
+    }
+}
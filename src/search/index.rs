@@ -0,0 +1,237 @@
+//! An inverted index from words to the documents containing them, kept up
+//! to date as snippets are added, edited and removed.
+//!
+//! Every suffix of every indexed word is kept sorted, so the words a query
+//! word equals, begins or appears in are found by a range lookup, in
+//! logarithmic time. Abbreviations and typos can't be looked up that way,
+//! so the words of titles, tags and descriptions, the only places the
+//! scoring accepts them, are also walked one by one; code words, usually
+//! the bulk of the vocabulary, are not. The index only narrows: candidates
+//! still go through the normal scoring, which decides whether they really
+//! match and how well.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::Bound;
+use std::rc::Rc;
+
+use super::words::{quality, words};
+
+/// Words longer than this, such as hex or base64 blobs in code, are not
+/// suffix-indexed, since a word has as many suffixes as characters; they
+/// are searched one by one instead.
+const MAX_SUFFIXED_CHARS: usize = 64;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchIndex {
+    /// Each word and the ids of the documents containing it.
+    postings: BTreeMap<Rc<str>, HashSet<String>>,
+    /// Each document's distinct words, so it can be taken out again.
+    documents: HashMap<String, DocumentWords>,
+    /// Every suffix of the words in `postings` up to [`MAX_SUFFIXED_CHARS`]
+    /// long, paired with its word.
+    suffixes: BTreeSet<(Box<str>, Rc<str>)>,
+    /// The words in `postings` too long to be in `suffixes`.
+    long_words: HashSet<Rc<str>>,
+    /// The words of titles, tags and descriptions and the ids of the
+    /// documents using them there.
+    prose: BTreeMap<Rc<str>, HashSet<String>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct DocumentWords {
+    all: Vec<Rc<str>>,
+    prose: Vec<Rc<str>>,
+}
+
+impl SearchIndex {
+    /// Indexes the words of `prose` (title, tags and description) and
+    /// `code` under `id`, replacing whatever was indexed for it before.
+    pub fn insert<'a>(
+        &mut self,
+        id: &str,
+        prose: impl IntoIterator<Item = &'a str>,
+        code: impl IntoIterator<Item = &'a str>,
+    ) {
+        self.remove(id);
+        let prose = distinct(prose.into_iter().flat_map(words));
+        let all = distinct(prose.iter().cloned().chain(code.into_iter().flat_map(words)));
+        let all: Vec<Rc<str>> = all.into_iter().map(|word| self.add_posting(&word, id)).collect();
+        let prose: Vec<Rc<str>> = prose
+            .iter()
+            .map(|word| {
+                let (word, _) = self.postings.get_key_value(word.as_str()).expect("indexed above");
+                self.prose.entry(word.clone()).or_default().insert(id.to_string());
+                word.clone()
+            })
+            .collect();
+        self.documents.insert(id.to_string(), DocumentWords { all, prose });
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        let Some(DocumentWords { all, prose }) = self.documents.remove(id) else {
+            return;
+        };
+        for word in prose {
+            if let Some(ids) = self.prose.get_mut(&word) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.prose.remove(&word);
+                }
+            }
+        }
+        for word in all {
+            let Some(ids) = self.postings.get_mut(&word) else {
+                continue;
+            };
+            ids.remove(id);
+            if ids.is_empty() {
+                self.postings.remove(&word);
+                if !self.long_words.remove(&word) {
+                    for suffix in suffixes(&word) {
+                        self.suffixes.remove(&(suffix.into(), word.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Ids of the documents that could match every word of `text` under
+    /// fuzzy matching: any document with a word it equals, begins or
+    /// appears in, or a title, tag or description word it abbreviates or
+    /// misspells. `None` when `text` has no words to narrow by.
+    pub fn candidates(&self, text: &str) -> Option<HashSet<&str>> {
+        words(text).iter().map(|word| self.word_candidates(word)).reduce(|found, next| {
+            found.intersection(&next).copied().collect()
+        })
+    }
+
+    fn word_candidates(&self, word: &str) -> HashSet<&str> {
+        let from: (Box<str>, Rc<str>) = (word.into(), "".into());
+        let containing = self.suffixes
+            .range((Bound::Included(from), Bound::Unbounded))
+            .take_while(|(suffix, _)| suffix.starts_with(word))
+            .map(|(_, candidate)| &self.postings[candidate]);
+        let long = self.long_words
+            .iter()
+            .filter(|candidate| candidate.contains(word))
+            .map(|candidate| &self.postings[candidate]);
+        let loose = self.prose
+            .iter()
+            .filter(|(candidate, _)| quality(word, candidate, true).is_some())
+            .map(|(_, ids)| ids);
+        containing
+            .chain(long)
+            .chain(loose)
+            .flat_map(|ids| ids.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Adds `id` to `word`'s posting, indexing the word if it is new, and
+    /// returns the shared copy of it.
+    fn add_posting(&mut self, word: &str, id: &str) -> Rc<str> {
+        let existing = self.postings.get_key_value(word).map(|(word, _)| word.clone());
+        if let Some(word) = existing {
+            self.postings.entry(word.clone()).or_default().insert(id.to_string());
+            return word;
+        }
+        let word: Rc<str> = word.into();
+        if word.chars().count() > MAX_SUFFIXED_CHARS {
+            self.long_words.insert(word.clone());
+        } else {
+            self.suffixes.extend(suffixes(&word).map(|suffix| (suffix.into(), word.clone())));
+        }
+        self.postings.insert(word.clone(), HashSet::from([id.to_string()]));
+        word
+    }
+}
+
+/// The distinct words among `words`, sorted.
+fn distinct(words: impl Iterator<Item = String>) -> Vec<String> {
+    let mut words: Vec<String> = words.collect();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+/// `word` and each of its tails, one per character.
+fn suffixes(word: &str) -> impl Iterator<Item = &str> {
+    word.char_indices().map(move |(at, _)| &word[at..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SearchIndex {
+        let mut index = SearchIndex::default();
+        index.insert("map", ["HashMap insert"], ["let mut map = HashMap::new();"]);
+        index.insert("mapping", ["Mapping iterators"], ["xs.iter().map(f)"]);
+        index.insert("list", ["Python List Comprehension"], ["[x for x in xs]"]);
+        index
+    }
+
+    fn ids(found: Option<HashSet<&str>>) -> Vec<&str> {
+        let mut ids: Vec<&str> = found.unwrap_or_default().into_iter().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn finds_words_containing_the_query_word() {
+        let index = index();
+        assert_eq!(ids(index.candidates("map")), ["map", "mapping"]);
+        assert_eq!(ids(index.candidates("mapp")), ["mapping"]);
+        assert_eq!(ids(index.candidates("ashmap")), ["map"]);
+        assert_eq!(ids(index.candidates("hash map")), ["map"]);
+    }
+
+    #[test]
+    fn finds_abbreviations_and_typos_of_prose_words() {
+        let index = index();
+        assert_eq!(ids(index.candidates("cmprh")), ["list"]);
+        assert_eq!(ids(index.candidates("comprehenison")), ["list"]);
+        assert_eq!(ids(index.candidates("dictionary")), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn abbreviations_still_count_when_another_document_has_the_word() {
+        let mut index = index();
+        index.insert("sort", ["Sort in place"], ["lst.sort()"]);
+        assert_eq!(ids(index.candidates("lst")), ["list", "sort"]);
+        assert_eq!(ids(index.candidates("lst cmprh")), ["list"]);
+    }
+
+    #[test]
+    fn code_words_only_match_verbatim() {
+        let mut index = SearchIndex::default();
+        index.insert("a", ["Spawn"], ["thread::spawn(worker)"]);
+        assert_eq!(ids(index.candidates("orke")), ["a"]);
+        assert_eq!(ids(index.candidates("wroker")), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn long_words_are_searched_without_suffixes() {
+        let blob = "0123456789abcdef".repeat(1_000);
+        let mut index = SearchIndex::default();
+        index.insert("blob", ["Test vector"], [blob.as_str()]);
+        assert!(index.suffixes.len() < 64);
+        assert_eq!(ids(index.candidates("89abcdef0123")), ["blob"]);
+        index.remove("blob");
+        assert!(index.long_words.is_empty() && index.suffixes.is_empty() && index.prose.is_empty());
+    }
+
+    #[test]
+    fn no_words_means_no_narrowing() {
+        assert_eq!(index().candidates("=> ::"), None);
+    }
+
+    #[test]
+    fn remove_and_reinsert_update_the_postings() {
+        let mut index = index();
+        index.remove("map");
+        assert_eq!(ids(index.candidates("hashmap")), Vec::<&str>::new());
+        index.insert("list", ["Vec of strings"], []);
+        assert_eq!(ids(index.candidates("python")), Vec::<&str>::new());
+        assert_eq!(ids(index.candidates("vec")), ["list"]);
+    }
+}
@@ -0,0 +1,66 @@
+//! Case-insensitive substring search over snippets, with the match
+//! positions needed to highlight results.
+
+pub mod fuzzy;
+pub mod index;
+pub mod query;
+pub mod words;
+
+use crate::Snippet;
+
+/// Whether `query` appears in the snippet's title, description, code or tags.
+pub fn matches(snippet: &Snippet, query: &str) -> bool {
+    query.is_empty()
+        || find(&snippet.title, query).is_some()
+        || snippet.description.as_deref().is_some_and(|d| find(d, query).is_some())
+        || find(&snippet.code, query).is_some()
+        || snippet.tags.iter().any(|tag| find(tag, query).is_some())
+}
+
+/// Byte range of the first case-insensitive occurrence of `query` in `text`.
+///
+/// Compares lowercased characters one by one rather than lowercasing both
+/// strings, so the range always lands on `text`'s own char boundaries.
+pub fn find(text: &str, query: &str) -> Option<(usize, usize)> {
+    if query.is_empty() {
+        return None;
+    }
+    text.char_indices().find_map(|(start, _)| {
+        let mut rest = text[start..].chars();
+        let mut consumed = 0;
+        for wanted in query.chars().flat_map(char::to_lowercase) {
+            let found = rest.next()?;
+            let mut lower = found.to_lowercase();
+            if lower.next() != Some(wanted) || lower.next().is_some() {
+                return None;
+            }
+            consumed += found.len_utf8();
+        }
+        Some((start, start + consumed))
+    })
+}
+
+/// Splits `text` into runs, flagging the ones that match `query`.
+pub fn segments<'a>(text: &'a str, query: &str) -> Vec<(&'a str, bool)> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some((start, end)) = find(rest, query) {
+        if start > 0 {
+            segments.push((&rest[..start], false));
+        }
+        segments.push((&rest[start..end], true));
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() {
+        segments.push((rest, false));
+    }
+    segments
+}
+
+/// The first line of `code` containing `query`, with its 1-based number.
+pub fn matching_line<'a>(code: &'a str, query: &str) -> Option<(usize, &'a str)> {
+    code.lines()
+        .enumerate()
+        .find(|(_, line)| find(line, query).is_some())
+        .map(|(index, line)| (index + 1, line.trim()))
+}
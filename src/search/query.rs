@@ -0,0 +1,502 @@
+//! The search box's query language.
+//!
+//! ```text
+//! lang:rust tag:async fav:true "exact phrase" -deprecated updated:>2024-06-01
+//! re:"fn \w+\(" -re:unwrap
+//! ```
+//!
+//! Whitespace separates clauses and a snippet must match all of them. Bare
+//! words are matched fuzzily and ranked (see [`super::fuzzy`]), quoted
+//! phrases only verbatim; `-` in front of any clause excludes what it
+//! matches, taken literally.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::NaiveDate;
+use regex::Regex;
+
+use crate::{tags, Snippet};
+
+use super::fuzzy;
+use super::index::SearchIndex;
+
+/// Filters the query language understands, as shown in error messages.
+const KNOWN_FIELDS: &str = "lang:, tag:, fav:, created:, updated:, re:";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub clauses: Vec<Clause>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clause {
+    pub negated: bool,
+    pub term: Term,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Term {
+    /// A bare word.
+    Text(String),
+    /// A `"quoted phrase"`, matched as a whole including its spaces.
+    Phrase(String),
+    /// `lang:rust`, compared case-insensitively.
+    Language(String),
+    /// `tag:async`, matching a whole tag rather than part of one. A
+    /// hierarchical tag matches its descendants too: `tag:web` finds
+    /// `web/react/hooks`.
+    Tag(String),
+    /// `fav:true` or `fav:false`.
+    Favorite(bool),
+    /// `updated:>2024-06-01`, compared by UTC calendar day.
+    Date { field: DateField, comparison: Comparison, date: NaiveDate },
+    /// `re:pattern`, matching any single line of the code.
+    Regex(Pattern),
+}
+
+/// A compiled regular expression, compared by its source.
+#[derive(Clone, Debug)]
+pub struct Pattern(Regex);
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateField {
+    Created,
+    Updated,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Before,
+    AtOrBefore,
+    On,
+    AtOrAfter,
+    After,
+}
+
+/// Why a query could not be parsed. Positions count characters from 1.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryError {
+    UnterminatedQuote { position: usize },
+    DanglingNegation { position: usize },
+    EmptyPhrase { position: usize },
+    EmptyValue { field: String },
+    UnknownField { field: String },
+    InvalidFavorite { value: String },
+    InvalidDate { field: String, value: String },
+    InvalidRegex { reason: String },
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::UnterminatedQuote { position } => {
+                write!(f, "The quote at character {position} is never closed")
+            }
+            QueryError::DanglingNegation { position } => {
+                write!(f, "The \"-\" at character {position} must be followed by something to exclude")
+            }
+            QueryError::EmptyPhrase { position } => {
+                write!(f, "The quotes at character {position} are empty")
+            }
+            QueryError::EmptyValue { field } => write!(f, "\"{field}:\" needs a value"),
+            QueryError::UnknownField { field } => write!(
+                f,
+                "Unknown filter \"{field}:\" (try {KNOWN_FIELDS}); put it in quotes to search for the text"
+            ),
+            QueryError::InvalidFavorite { value } => {
+                write!(f, "fav: takes true or false, not \"{value}\"")
+            }
+            QueryError::InvalidDate { field, value } => write!(
+                f,
+                "{field}: takes a date like 2024-06-01, optionally after >, >=, < or <=, not \"{value}\""
+            ),
+            QueryError::InvalidRegex { reason } => write!(f, "Invalid regular expression: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Query {
+    /// How relevant `snippet` is, higher is better, or `None` if it fails a
+    /// clause. Only text and phrases add to the score; filters just gate, and
+    /// an empty query matches everything.
+    pub fn score(&self, snippet: &Snippet) -> Option<u32> {
+        let mut total = 0;
+        for clause in &self.clauses {
+            total += match (&clause.term, clause.negated) {
+                (Term::Text(text), false) => fuzzy::score(snippet, text),
+                (Term::Phrase(phrase), false) => fuzzy::phrase_score(snippet, phrase),
+                (term, negated) => (term.matches(snippet) != negated).then_some(0),
+            }?;
+        }
+        Some(total)
+    }
+
+    /// Also requires `language`, unless it is empty.
+    pub fn in_language(mut self, language: &str) -> Self {
+        if !language.is_empty() {
+            let term = Term::Language(language.to_string());
+            self.clauses.push(Clause { negated: false, term });
+        }
+        self
+    }
+
+    /// The snippets outside the trash that satisfy the query, most relevant
+    /// first and otherwise in vault order. Only the index's candidates are
+    /// scored.
+    pub fn rank<'a>(
+        &self,
+        snippets: impl IntoIterator<Item = &'a Snippet>,
+        index: &SearchIndex,
+    ) -> Vec<&'a Snippet> {
+        let candidates = self.candidates(index);
+        let mut ranked: Vec<(u32, &Snippet)> = snippets.into_iter()
+            .filter(|snippet| !snippet.is_trashed())
+            .filter(|snippet| candidates.as_ref().is_none_or(|ids| ids.contains(snippet.id.as_str())))
+            .filter_map(|snippet| Some((self.score(snippet)?, snippet)))
+            .collect();
+        // Stable, so equally relevant snippets keep their vault order.
+        ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+        ranked.into_iter().map(|(_, snippet)| snippet).collect()
+    }
+
+    /// Ids of the snippets that could satisfy the query's text and phrases,
+    /// or `None` if it has neither and every snippet needs checking.
+    pub fn candidates<'a>(&self, index: &'a SearchIndex) -> Option<HashSet<&'a str>> {
+        self.clauses.iter()
+            .filter(|clause| !clause.negated)
+            .filter_map(|clause| match &clause.term {
+                Term::Text(text) | Term::Phrase(text) => index.candidates(text),
+                _ => None,
+            })
+            .reduce(|found, next| found.intersection(&next).copied().collect())
+    }
+
+    /// Lines of `code` matched by the query's regexes, numbered from 1.
+    pub fn matching_lines<'a>(&self, code: &'a str) -> Vec<(usize, &'a str)> {
+        let patterns: Vec<&Regex> = self.clauses.iter()
+            .filter(|clause| !clause.negated)
+            .filter_map(|clause| match &clause.term {
+                Term::Regex(Pattern(regex)) => Some(regex),
+                _ => None,
+            })
+            .collect();
+        if patterns.is_empty() {
+            return Vec::new();
+        }
+        code.lines()
+            .enumerate()
+            .filter(|(_, line)| patterns.iter().any(|regex| regex.is_match(line)))
+            .map(|(index, line)| (index + 1, line))
+            .collect()
+    }
+}
+
+impl Term {
+    fn matches(&self, snippet: &Snippet) -> bool {
+        match self {
+            Term::Text(text) | Term::Phrase(text) => super::matches(snippet, text),
+            Term::Language(language) => snippet.language.eq_ignore_ascii_case(language),
+            Term::Tag(tag) => snippet.tags.iter().any(|t| tags::is_within(t, tag)),
+            Term::Favorite(favorite) => snippet.is_favorite == *favorite,
+            Term::Date { field, comparison, date } => {
+                let at = match field {
+                    DateField::Created => snippet.created_at,
+                    DateField::Updated => snippet.updated_at,
+                };
+                let day = at.date_naive();
+                match comparison {
+                    Comparison::Before => day < *date,
+                    Comparison::AtOrBefore => day <= *date,
+                    Comparison::On => day == *date,
+                    Comparison::AtOrAfter => day >= *date,
+                    Comparison::After => day > *date,
+                }
+            }
+            Term::Regex(Pattern(regex)) => snippet.code.lines().any(|line| regex.is_match(line)),
+        }
+    }
+}
+
+/// The whole of `input` as a single regular expression, for the search
+/// box's regex mode.
+pub fn parse_regex(input: &str) -> Result<Query, QueryError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(Query::default());
+    }
+    let term = Term::Regex(compile(input)?);
+    Ok(Query { clauses: vec![Clause { negated: false, term }] })
+}
+
+pub fn parse(input: &str) -> Result<Query, QueryError> {
+    let mut parser = Parser { input, pos: 0 };
+    let mut clauses = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        let Some(first) = parser.peek() else {
+            break;
+        };
+        let start = parser.pos;
+        let negated = first == '-';
+        if negated {
+            parser.bump();
+            if parser.peek().is_none_or(char::is_whitespace) {
+                return Err(QueryError::DanglingNegation { position: parser.position(start) });
+            }
+        }
+        let term = parser.term()?;
+        clauses.push(Clause { negated, term });
+    }
+    Ok(Query { clauses })
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// The 1-based character position of byte offset `at`, for error messages.
+    fn position(&self, at: usize) -> usize {
+        self.input[..at].chars().count() + 1
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Everything up to the next whitespace.
+    fn word(&mut self) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| !c.is_whitespace()) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    /// The contents of a `"quoted"` run, with the cursor on the opening quote.
+    fn quoted(&mut self) -> Result<&'a str, QueryError> {
+        let open = self.pos;
+        self.bump();
+        let start = self.pos;
+        let Some(len) = self.input[start..].find('"') else {
+            return Err(QueryError::UnterminatedQuote { position: self.position(open) });
+        };
+        self.pos = start + len + 1;
+        Ok(&self.input[start..start + len])
+    }
+
+    fn term(&mut self) -> Result<Term, QueryError> {
+        let start = self.pos;
+        if self.peek() == Some('"') {
+            let phrase = self.quoted()?;
+            if phrase.trim().is_empty() {
+                return Err(QueryError::EmptyPhrase { position: self.position(start) });
+            }
+            return Ok(Term::Phrase(phrase.to_string()));
+        }
+
+        // `name:value` is a filter only when `name` is a plain word, so code
+        // like `std::io` or a URL is still searched as text.
+        let name_len = self.input[start..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(self.input.len() - start);
+        let after_name = &self.input[start + name_len..];
+        let is_field = name_len > 0
+            && after_name.starts_with(':')
+            && !after_name[1..].starts_with([':', '/']);
+        if !is_field {
+            return Ok(Term::Text(self.word().to_string()));
+        }
+
+        let field = self.input[start..start + name_len].to_ascii_lowercase();
+        self.pos = start + name_len + 1;
+        let value = if self.peek() == Some('"') { self.quoted()? } else { self.word() };
+        if value.trim().is_empty() {
+            return Err(QueryError::EmptyValue { field });
+        }
+        field_term(field, value)
+    }
+}
+
+fn field_term(field: String, value: &str) -> Result<Term, QueryError> {
+    match field.as_str() {
+        "lang" | "language" => Ok(Term::Language(value.to_string())),
+        "tag" => Ok(Term::Tag(value.trim_start_matches('#').to_string())),
+        "fav" | "favorite" => match value.to_ascii_lowercase().as_str() {
+            "true" | "yes" => Ok(Term::Favorite(true)),
+            "false" | "no" => Ok(Term::Favorite(false)),
+            _ => Err(QueryError::InvalidFavorite { value: value.to_string() }),
+        },
+        "created" | "updated" => {
+            let (comparison, date) = if let Some(date) = value.strip_prefix(">=") {
+                (Comparison::AtOrAfter, date)
+            } else if let Some(date) = value.strip_prefix("<=") {
+                (Comparison::AtOrBefore, date)
+            } else if let Some(date) = value.strip_prefix('>') {
+                (Comparison::After, date)
+            } else if let Some(date) = value.strip_prefix('<') {
+                (Comparison::Before, date)
+            } else {
+                (Comparison::On, value.strip_prefix('=').unwrap_or(value))
+            };
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| QueryError::InvalidDate { field: field.clone(), value: value.to_string() })?;
+            let field = if field == "created" { DateField::Created } else { DateField::Updated };
+            Ok(Term::Date { field, comparison, date })
+        }
+        "re" => compile(value).map(Term::Regex),
+        _ => Err(QueryError::UnknownField { field }),
+    }
+}
+
+fn compile(pattern: &str) -> Result<Pattern, QueryError> {
+    Regex::new(pattern).map(Pattern).map_err(|err| {
+        // Syntax errors span several lines with a caret diagram; the
+        // last line is the part worth showing under the search box.
+        let message = err.to_string();
+        let reason = message.lines().last().unwrap_or_default();
+        QueryError::InvalidRegex { reason: reason.trim_start_matches("error: ").to_string() }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clause(term: Term) -> Clause {
+        Clause { negated: false, term }
+    }
+
+    fn not(term: Term) -> Clause {
+        Clause { negated: true, term }
+    }
+
+    fn text(text: &str) -> Term {
+        Term::Text(text.to_string())
+    }
+
+    fn date(field: DateField, comparison: Comparison, day: &str) -> Term {
+        let date = NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap();
+        Term::Date { field, comparison, date }
+    }
+
+    fn regex(pattern: &str) -> Term {
+        Term::Regex(compile(pattern).unwrap())
+    }
+
+    #[test]
+    fn parses_clauses() {
+        let cases: Vec<(&str, Vec<Clause>)> = vec![
+            ("", vec![]),
+            ("   ", vec![]),
+            ("hash map", vec![clause(text("hash")), clause(text("map"))]),
+            ("lang:rust", vec![clause(Term::Language("rust".into()))]),
+            ("Language:Go", vec![clause(Term::Language("Go".into()))]),
+            ("tag:#async", vec![clause(Term::Tag("async".into()))]),
+            ("tag:web/react", vec![clause(Term::Tag("web/react".into()))]),
+            ("fav:true", vec![clause(Term::Favorite(true))]),
+            ("favorite:no", vec![clause(Term::Favorite(false))]),
+            ("lang:\"C Sharp\"", vec![clause(Term::Language("C Sharp".into()))]),
+            ("\"hash map\"", vec![clause(Term::Phrase("hash map".into()))]),
+            ("-deprecated", vec![not(text("deprecated"))]),
+            ("-\"old api\"", vec![not(Term::Phrase("old api".into()))]),
+            ("-lang:python", vec![not(Term::Language("python".into()))]),
+            ("updated:2024-06-01", vec![clause(date(DateField::Updated, Comparison::On, "2024-06-01"))]),
+            ("updated:=2024-06-01", vec![clause(date(DateField::Updated, Comparison::On, "2024-06-01"))]),
+            ("created:>2024-06-01", vec![clause(date(DateField::Created, Comparison::After, "2024-06-01"))]),
+            ("created:>=2024-06-01", vec![clause(date(DateField::Created, Comparison::AtOrAfter, "2024-06-01"))]),
+            ("updated:<2024-06-01", vec![clause(date(DateField::Updated, Comparison::Before, "2024-06-01"))]),
+            ("updated:<=2024-06-01", vec![clause(date(DateField::Updated, Comparison::AtOrBefore, "2024-06-01"))]),
+            ("re:\"fn \\w+\\(\"", vec![clause(regex("fn \\w+\\("))]),
+            ("-re:unwrap", vec![not(regex("unwrap"))]),
+            ("std::io", vec![clause(text("std::io"))]),
+            ("https://example.com/a", vec![clause(text("https://example.com/a"))]),
+            ("a-b", vec![clause(text("a-b"))]),
+            ("=>", vec![clause(text("=>"))]),
+            (
+                "lang:rust tag:async \"exact phrase\" -deprecated",
+                vec![
+                    clause(Term::Language("rust".into())),
+                    clause(Term::Tag("async".into())),
+                    clause(Term::Phrase("exact phrase".into())),
+                    not(text("deprecated")),
+                ],
+            ),
+        ];
+        for (input, clauses) in cases {
+            assert_eq!(parse(input), Ok(Query { clauses }), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn reports_errors() {
+        let cases = [
+            ("say \"hi", QueryError::UnterminatedQuote { position: 5 }, "The quote at character 5 is never closed"),
+            ("a - b", QueryError::DanglingNegation { position: 3 }, "The \"-\" at character 3 must be followed by something to exclude"),
+            ("x -", QueryError::DanglingNegation { position: 3 }, "The \"-\" at character 3 must be followed by something to exclude"),
+            ("é \"  \"", QueryError::EmptyPhrase { position: 3 }, "The quotes at character 3 are empty"),
+            ("lang:", QueryError::EmptyValue { field: "lang".into() }, "\"lang:\" needs a value"),
+            ("tag:\"\"", QueryError::EmptyValue { field: "tag".into() }, "\"tag:\" needs a value"),
+            (
+                "author:me",
+                QueryError::UnknownField { field: "author".into() },
+                "Unknown filter \"author:\" (try lang:, tag:, fav:, created:, updated:, re:); put it in quotes to search for the text",
+            ),
+            ("fav:maybe", QueryError::InvalidFavorite { value: "maybe".into() }, "fav: takes true or false, not \"maybe\""),
+            (
+                "updated:>yesterday",
+                QueryError::InvalidDate { field: "updated".into(), value: ">yesterday".into() },
+                "updated: takes a date like 2024-06-01, optionally after >, >=, < or <=, not \">yesterday\"",
+            ),
+            (
+                "created:2024-13-01",
+                QueryError::InvalidDate { field: "created".into(), value: "2024-13-01".into() },
+                "created: takes a date like 2024-06-01, optionally after >, >=, < or <=, not \"2024-13-01\"",
+            ),
+        ];
+        for (input, error, message) in cases {
+            let parsed = parse(input);
+            assert_eq!(parsed, Err(error), "{input:?}");
+            assert_eq!(parsed.unwrap_err().to_string(), message, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn reports_invalid_regexes_briefly() {
+        for parsed in [parse("re:fn(\\w+"), parse_regex("fn(\\w+")] {
+            let Err(error @ QueryError::InvalidRegex { reason }) = &parsed else {
+                panic!("expected an invalid regex, got {parsed:?}");
+            };
+            assert!(!reason.is_empty() && !reason.contains('\n'));
+            assert_eq!(error.to_string(), format!("Invalid regular expression: {reason}"));
+        }
+    }
+
+    #[test]
+    fn regex_mode_takes_the_whole_input() {
+        assert_eq!(parse_regex("  "), Ok(Query::default()));
+        assert_eq!(parse_regex(" fn main "), Ok(Query { clauses: vec![clause(regex("fn main"))] }));
+    }
+}
@@ -0,0 +1,92 @@
+//! Word-level matching shared by the fuzzy ranking and the search index.
+//!
+//! Nothing here knows about snippets, so the index benchmark can build it
+//! on its own.
+
+/// How closely a query word matched, worst to best.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Quality {
+    Typo = 1,
+    Abbreviation = 2,
+    Substring = 3,
+    Prefix = 4,
+    Exact = 5,
+}
+
+impl Quality {
+    pub fn weighted(self, weight: u32) -> u32 {
+        self as u32 * weight
+    }
+}
+
+/// Lowercased alphanumeric runs, so `List-Comprehension` and `list_comprehension`
+/// both give `list` and `comprehension`.
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// How well `word` matches `candidate`, both already lowercased.
+pub fn quality(word: &str, candidate: &str, abbreviations: bool) -> Option<Quality> {
+    if candidate == word {
+        Some(Quality::Exact)
+    } else if candidate.starts_with(word) {
+        Some(Quality::Prefix)
+    } else if candidate.contains(word) {
+        Some(Quality::Substring)
+    } else if abbreviations && is_abbreviation(word, candidate) {
+        Some(Quality::Abbreviation)
+    } else if within_typo_distance(word, candidate) {
+        Some(Quality::Typo)
+    } else {
+        None
+    }
+}
+
+/// `cmprh` abbreviates `comprehension`: same first letter, the rest in order.
+fn is_abbreviation(word: &str, candidate: &str) -> bool {
+    let mut rest = candidate.chars();
+    let mut wanted = word.chars();
+    if word.chars().count() < 2 || wanted.next() != rest.next() {
+        return false;
+    }
+    wanted.all(|c| rest.any(|r| r == c))
+}
+
+/// Allows one typo in words of four letters or more and two from eight on;
+/// shorter words are too easy to mistake for something else.
+fn within_typo_distance(word: &str, candidate: &str) -> bool {
+    let word: Vec<char> = word.chars().collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+    let allowed = match word.len() {
+        ..=3 => return false,
+        4..=7 => 1,
+        _ => 2,
+    };
+    word.len().abs_diff(candidate.len()) <= allowed && edit_distance(&word, &candidate) <= allowed
+}
+
+/// Edit distance counting a swap of neighbouring letters as one edit, since
+/// that is the most common typo.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in table.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in table[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = table[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut best = substitution.min(table[i - 1][j] + 1).min(table[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(table[i - 2][j - 2] + 1);
+            }
+            table[i][j] = best;
+        }
+    }
+    table[a.len()][b.len()]
+}
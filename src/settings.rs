@@ -0,0 +1,274 @@
+//! User preferences, saved alongside the vault with the same backend.
+
+use std::cmp::Reverse;
+
+use chrono::{DateTime, Duration, Utc};
+use dioxus::logger::tracing;
+use serde::{Deserialize, Serialize};
+
+use crate::ai::ExplainConfig;
+use crate::search::query::{self, Query, QueryError};
+use crate::store::schema::{self, Format};
+use crate::store::{self, Backend};
+use crate::Snippet;
+
+/// Changing a field's meaning or shape needs a migration step here.
+const FORMAT: Format = Format { field: "settings", migrations: &[schema::enveloped] };
+
+/// Every field has a default so settings saved by older builds still load.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub explain: ExplainConfig,
+    pub code_theme: CodeTheme,
+    /// Days a trashed snippet is kept before being purged; 0 keeps it forever.
+    pub trash_retention_days: u32,
+    /// Searches the user opened a result from, most recent first.
+    pub recent_searches: Vec<String>,
+    /// Named searches shown as chips on the home screen.
+    pub saved_searches: Vec<SavedSearch>,
+    pub tag_rules: TagRules,
+    /// Order of the home screen list, unless the URL asks for another.
+    pub sort_order: SortOrder,
+}
+
+/// How many recent searches are remembered.
+const MAX_RECENT_SEARCHES: usize = 8;
+
+/// The longest trash retention the settings form accepts, a century.
+pub const MAX_TRASH_RETENTION_DAYS: u32 = 36_500;
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            explain: ExplainConfig::default(),
+            code_theme: CodeTheme::default(),
+            trash_retention_days: 30,
+            recent_searches: Vec::new(),
+            saved_searches: Vec::new(),
+            tag_rules: TagRules::default(),
+            sort_order: SortOrder::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Snippets trashed before this are due for purging, if retention is
+    /// limited. A retention reaching back before any representable date
+    /// purges nothing.
+    pub fn trash_cutoff(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.trash_retention_days == 0 {
+            return None;
+        }
+        Duration::try_days(i64::from(self.trash_retention_days))
+            .and_then(|retention| now.checked_sub_signed(retention))
+    }
+
+    /// Moves `query` to the front of the recent searches.
+    pub fn remember_search(&mut self, query: &str) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.recent_searches.retain(|recent| recent != query);
+        self.recent_searches.insert(0, query.to_string());
+        self.recent_searches.truncate(MAX_RECENT_SEARCHES);
+    }
+
+    /// Adds `search`, replacing a saved search with the same name.
+    pub fn save_search(&mut self, search: SavedSearch) {
+        match self.saved_searches.iter_mut().find(|saved| saved.name == search.name) {
+            Some(saved) => *saved = search,
+            None => self.saved_searches.push(search),
+        }
+    }
+}
+
+/// A home screen search kept under a name, a "smart collection". Only the
+/// query is stored, so what it shows follows the vault as it changes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    /// Language chip selected with the query; empty for all languages.
+    #[serde(default)]
+    pub language: String,
+    /// Whether `query` is a regular expression rather than the query language.
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl SavedSearch {
+    pub fn parse(&self) -> Result<Query, QueryError> {
+        let parsed = if self.regex { query::parse_regex(&self.query) } else { query::parse(&self.query) };
+        parsed.map(|query| query.in_language(&self.language))
+    }
+}
+
+/// How typed tags are cleaned up before they are added to a snippet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagRules {
+    /// Characters that finish a tag while typing, as Enter does.
+    pub separators: String,
+    pub lowercase: bool,
+    /// What runs of spaces inside a tag become; empty keeps a single space.
+    pub space_replacement: String,
+}
+
+impl Default for TagRules {
+    fn default() -> Self {
+        Self {
+            separators: ",".into(),
+            lowercase: true,
+            space_replacement: "-".into(),
+        }
+    }
+}
+
+/// How the home screen orders its snippets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// Most relevant first while searching, vault order otherwise.
+    #[default]
+    BestMatch,
+    RecentlyUpdated,
+    RecentlyCreated,
+    Title,
+    MostCopied,
+    MostViewed,
+    FavoritesFirst,
+    Language,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 8] = [
+        SortOrder::BestMatch,
+        SortOrder::RecentlyUpdated,
+        SortOrder::RecentlyCreated,
+        SortOrder::Title,
+        SortOrder::MostCopied,
+        SortOrder::MostViewed,
+        SortOrder::FavoritesFirst,
+        SortOrder::Language,
+    ];
+
+    /// The `?sort=` value in the home screen URL.
+    pub fn key(self) -> &'static str {
+        match self {
+            SortOrder::BestMatch => "best",
+            SortOrder::RecentlyUpdated => "updated",
+            SortOrder::RecentlyCreated => "created",
+            SortOrder::Title => "title",
+            SortOrder::MostCopied => "copied",
+            SortOrder::MostViewed => "viewed",
+            SortOrder::FavoritesFirst => "favorites",
+            SortOrder::Language => "language",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<SortOrder> {
+        SortOrder::ALL.into_iter().find(|order| order.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::BestMatch => "Best match",
+            SortOrder::RecentlyUpdated => "Recently updated",
+            SortOrder::RecentlyCreated => "Recently created",
+            SortOrder::Title => "Title A–Z",
+            SortOrder::MostCopied => "Most copied",
+            SortOrder::MostViewed => "Most viewed",
+            SortOrder::FavoritesFirst => "Favorites first",
+            SortOrder::Language => "Language",
+        }
+    }
+
+    /// Sorts `snippets` in place. The sort is stable, so snippets that tie
+    /// keep the order they came in, which is by relevance while searching.
+    pub fn sort(self, snippets: &mut [&Snippet]) {
+        match self {
+            SortOrder::BestMatch => {}
+            SortOrder::RecentlyUpdated => snippets.sort_by_key(|s| Reverse(s.updated_at)),
+            SortOrder::RecentlyCreated => snippets.sort_by_key(|s| Reverse(s.created_at)),
+            SortOrder::Title => snippets.sort_by_cached_key(|s| s.title.to_lowercase()),
+            SortOrder::MostCopied => snippets.sort_by_key(|s| Reverse(s.copy_count)),
+            SortOrder::MostViewed => snippets.sort_by_key(|s| Reverse(s.view_count)),
+            SortOrder::FavoritesFirst => snippets.sort_by_key(|s| !s.is_favorite),
+            SortOrder::Language => snippets.sort_by_cached_key(|s| s.language.to_lowercase()),
+        }
+    }
+}
+
+/// Colour scheme for highlighted code blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeTheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl CodeTheme {
+    pub const ALL: [CodeTheme; 2] = [CodeTheme::Dark, CodeTheme::Light];
+
+    pub fn class(self) -> &'static str {
+        match self {
+            CodeTheme::Dark => "theme-dark",
+            CodeTheme::Light => "theme-light",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CodeTheme::Dark => "Dark",
+            CodeTheme::Light => "Light",
+        }
+    }
+}
+
+/// The saved settings, the defaults if nothing has been saved yet, or why
+/// they could not be read.
+pub fn load() -> Result<Settings, store::StoreError> {
+    let loaded = match store::default_backend("settings").read() {
+        Ok(Some(raw)) => FORMAT.decode(&raw).map_err(store::StoreError::from),
+        Ok(None) => Ok(Settings::default()),
+        Err(err) => Err(err),
+    };
+    loaded.inspect_err(|err| tracing::error!("{err}; settings will not be saved this session"))
+}
+
+pub fn save(settings: &Settings) {
+    let result = FORMAT.encode(settings)
+        .map_err(|err| store::StoreError::Backend(err.to_string()))
+        .and_then(|raw| store::default_backend("settings").write(&raw));
+    if let Err(err) = result {
+        tracing::error!("{err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trash_cutoff_survives_huge_retention() {
+        let now = crate::time::now();
+        let settings = |days| Settings { trash_retention_days: days, ..Settings::default() };
+        assert_eq!(settings(0).trash_cutoff(now), None);
+        assert_eq!(settings(30).trash_cutoff(now), Some(now - Duration::days(30)));
+        assert_eq!(settings(100_000_000).trash_cutoff(now), None);
+        assert_eq!(settings(u32::MAX).trash_cutoff(now), None);
+    }
+
+    #[test]
+    fn reads_settings_saved_before_the_envelope() {
+        let legacy = r#"{"trash_retention_days": 7, "recent_searches": ["tokio"]}"#;
+        let settings: Settings = FORMAT.decode(legacy).unwrap();
+        assert_eq!(settings.trash_retention_days, 7);
+        assert_eq!(settings.recent_searches, ["tokio"]);
+        let saved = FORMAT.encode(&settings).unwrap();
+        assert_eq!(FORMAT.decode::<Settings>(&saved).unwrap(), settings);
+    }
+}
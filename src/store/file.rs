@@ -0,0 +1,51 @@
+use std::io;
+use std::path::PathBuf;
+
+use super::{Backend, PersistentStore, StoreError};
+
+/// The vault as a JSON file, used on desktop and mobile.
+pub type FileStore = PersistentStore<JsonFile>;
+
+pub struct JsonFile {
+    path: PathBuf,
+}
+
+impl JsonFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `<app data dir>/code-snippet-vault/<file_name>`, falling back to the
+    /// working directory on platforms without a known data directory.
+    pub fn in_data_dir(file_name: &str) -> Self {
+        let dir = dirs::data_dir()
+            .map(|dir| dir.join("code-snippet-vault"))
+            .unwrap_or_default();
+        Self::new(dir.join(file_name))
+    }
+}
+
+impl Default for JsonFile {
+    fn default() -> Self {
+        Self::in_data_dir("snippets.json")
+    }
+}
+
+impl Backend for JsonFile {
+    fn read(&self) -> Result<Option<String>, StoreError> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(raw) => Ok(Some(raw)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(StoreError::Backend(format!("{}: {err}", self.path.display()))),
+        }
+    }
+
+    fn write(&self, raw: &str) -> Result<(), StoreError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| StoreError::Backend(format!("{}: {err}", parent.display())))?;
+        }
+        std::fs::write(&self.path, raw)
+            .map_err(|err| StoreError::Backend(format!("{}: {err}", self.path.display())))
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+
+use crate::search::index::SearchIndex;
+use crate::{time, Snippet};
+
+use super::{SnippetStore, StoreError, Usage};
+
+/// A vault that lives only as long as the value does.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryStore {
+    snippets: Vec<Snippet>,
+    index: SearchIndex,
+}
+
+impl MemoryStore {
+    pub fn new(snippets: Vec<Snippet>) -> Self {
+        let mut index = SearchIndex::default();
+        for snippet in &snippets {
+            index_snippet(&mut index, snippet);
+        }
+        Self { snippets, index }
+    }
+
+    fn position(&self, id: &str) -> Result<usize, StoreError> {
+        self.snippets
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or_else(|| StoreError::NotFound(id.to_string()))
+    }
+}
+
+impl SnippetStore for MemoryStore {
+    fn list(&self) -> &[Snippet] {
+        &self.snippets
+    }
+
+    fn get(&self, id: &str) -> Option<&Snippet> {
+        self.snippets.iter().find(|s| s.id == id)
+    }
+
+    fn search_index(&self) -> &SearchIndex {
+        &self.index
+    }
+
+    fn insert(&mut self, snippet: Snippet) -> Result<(), StoreError> {
+        if self.get(&snippet.id).is_some() {
+            return Err(StoreError::DuplicateId(snippet.id));
+        }
+        index_snippet(&mut self.index, &snippet);
+        self.snippets.push(snippet);
+        Ok(())
+    }
+
+    fn update(&mut self, snippet: Snippet) -> Result<(), StoreError> {
+        let index = self.position(&snippet.id)?;
+        index_snippet(&mut self.index, &snippet);
+        self.snippets[index] = snippet;
+        Ok(())
+    }
+
+    fn trash(&mut self, id: &str) -> Result<(), StoreError> {
+        let index = self.position(id)?;
+        self.snippets[index].deleted_at = Some(time::now());
+        Ok(())
+    }
+
+    fn restore(&mut self, id: &str) -> Result<(), StoreError> {
+        let index = self.position(id)?;
+        self.snippets[index].deleted_at = None;
+        Ok(())
+    }
+
+    fn delete(&mut self, id: &str) -> Result<Snippet, StoreError> {
+        let index = self.position(id)?;
+        self.index.remove(id);
+        Ok(self.snippets.remove(index))
+    }
+
+    fn purge_trashed_before(&mut self, cutoff: DateTime<Utc>) -> Result<usize, StoreError> {
+        let before = self.snippets.len();
+        let index = &mut self.index;
+        self.snippets.retain(|s| {
+            let keep = s.deleted_at.is_none_or(|deleted_at| deleted_at >= cutoff);
+            if !keep {
+                index.remove(&s.id);
+            }
+            keep
+        });
+        Ok(before - self.snippets.len())
+    }
+
+    fn toggle_favorite(&mut self, id: &str) -> Result<bool, StoreError> {
+        let index = self.position(id)?;
+        let snippet = &mut self.snippets[index];
+        snippet.is_favorite = !snippet.is_favorite;
+        Ok(snippet.is_favorite)
+    }
+
+    fn record_use(&mut self, id: &str, usage: Usage) -> Result<(), StoreError> {
+        let index = self.position(id)?;
+        let snippet = &mut self.snippets[index];
+        let count = match usage {
+            Usage::View => &mut snippet.view_count,
+            Usage::Copy => &mut snippet.copy_count,
+        };
+        *count = count.saturating_add(1);
+        Ok(())
+    }
+}
+
+/// Everything the search looks at: title, description, code and tags.
+fn index_snippet(index: &mut SearchIndex, snippet: &Snippet) {
+    let description = snippet.description.as_deref().unwrap_or_default();
+    let prose = [snippet.title.as_str(), description].into_iter().chain(snippet.tags.iter().map(String::as_str));
+    index.insert(&snippet.id, prose, [snippet.code.as_str()]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> MemoryStore {
+        MemoryStore::new(vec![
+            Snippet::for_test("a", "Read a file", "std::fs::read_to_string(path)"),
+            Snippet::for_test("b", "Spawn a thread", "std::thread::spawn(|| {})"),
+        ])
+    }
+
+    #[test]
+    fn insert_rejects_a_duplicate_id() {
+        let mut store = store();
+        let duplicate = Snippet::for_test("a", "Another", "");
+        assert_eq!(store.insert(duplicate), Err(StoreError::DuplicateId("a".into())));
+        assert_eq!(store.list().len(), 2);
+        assert_eq!(store.get("a").unwrap().title, "Read a file");
+    }
+
+    #[test]
+    fn insert_appends_and_indexes() {
+        let mut store = store();
+        store.insert(Snippet::for_test("c", "Parse JSON", "serde_json::from_str(s)")).unwrap();
+        let ids: Vec<&str> = store.list().iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert!(store.search_index().candidates("serde").unwrap().contains("c"));
+    }
+
+    #[test]
+    fn missing_ids_are_not_found() {
+        let mut store = store();
+        let missing = StoreError::NotFound("zzz".into());
+        assert_eq!(store.update(Snippet::for_test("zzz", "Ghost", "")), Err(missing.clone()));
+        assert_eq!(store.delete("zzz"), Err(missing.clone()));
+        assert_eq!(store.trash("zzz"), Err(missing.clone()));
+        assert_eq!(store.restore("zzz"), Err(missing.clone()));
+        assert_eq!(store.toggle_favorite("zzz"), Err(missing.clone()));
+        assert_eq!(store.record_use("zzz", Usage::View), Err(missing));
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn update_replaces_in_place() {
+        let mut store = store();
+        store.update(Snippet::for_test("a", "Read a file to a string", "")).unwrap();
+        assert_eq!(store.list()[0].title, "Read a file to a string");
+        assert_eq!(store.list().len(), 2);
+    }
+
+    #[test]
+    fn delete_hands_the_snippet_back() {
+        let mut store = store();
+        let deleted = store.delete("a").unwrap();
+        assert_eq!(deleted.title, "Read a file");
+        assert_eq!(store.get("a"), None);
+        assert!(store.search_index().candidates("read").is_none_or(|ids| !ids.contains("a")));
+    }
+
+    #[test]
+    fn toggle_favorite_flips_and_returns_the_flag() {
+        let mut store = store();
+        assert_eq!(store.toggle_favorite("b"), Ok(true));
+        assert!(store.get("b").unwrap().is_favorite);
+        assert_eq!(store.toggle_favorite("b"), Ok(false));
+        assert!(!store.get("b").unwrap().is_favorite);
+        assert!(!store.get("a").unwrap().is_favorite);
+    }
+
+    #[test]
+    fn trash_keeps_the_snippet_until_purged() {
+        let mut store = store();
+        store.trash("a").unwrap();
+        assert!(store.get("a").unwrap().is_trashed());
+        store.restore("a").unwrap();
+        assert!(!store.get("a").unwrap().is_trashed());
+
+        store.trash("b").unwrap();
+        let purged = store.purge_trashed_before(time::now() + chrono::Duration::seconds(1)).unwrap();
+        assert_eq!(purged, 1);
+        assert_eq!(store.get("b"), None);
+    }
+
+    #[test]
+    fn record_use_bumps_only_the_counter() {
+        let mut store = store();
+        let before = store.get("a").unwrap().clone();
+        store.record_use("a", Usage::View).unwrap();
+        store.record_use("a", Usage::View).unwrap();
+        store.record_use("a", Usage::Copy).unwrap();
+        let after = store.get("a").unwrap();
+        assert_eq!((after.view_count, after.copy_count), (2, 1));
+        assert_eq!(after.updated_at, before.updated_at);
+        assert_eq!(store.get("b").unwrap().view_count, 0);
+    }
+}
@@ -0,0 +1,136 @@
+//! The snippet vault behind a swappable storage backend.
+//!
+//! The UI only talks to [`SnippetStore`]; which implementation sits behind it
+//! is decided per platform by [`open_default`]. [`MemoryStore`] keeps
+//! everything in memory and is what tests use, while [`PersistentStore`]
+//! wraps it and writes the full vault to a [`Backend`] after every change.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use dioxus::logger::tracing;
+
+use crate::search::index::SearchIndex;
+use crate::Snippet;
+
+mod memory;
+mod persistent;
+pub mod schema;
+#[cfg(not(target_arch = "wasm32"))]
+mod file;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+pub use memory::MemoryStore;
+pub use persistent::{Backend, PersistentStore};
+pub use schema::SchemaError;
+#[cfg(not(target_arch = "wasm32"))]
+pub use file::{FileStore, JsonFile};
+#[cfg(target_arch = "wasm32")]
+pub use web::{LocalStorage, WebStore};
+
+/// Operations the UI performs on the vault.
+pub trait SnippetStore {
+    /// All snippets in insertion order, including those in the trash.
+    fn list(&self) -> &[Snippet];
+
+    fn get(&self, id: &str) -> Option<&Snippet>;
+
+    /// Word index over every snippet, trashed ones included.
+    fn search_index(&self) -> &SearchIndex;
+
+    /// Adds a new snippet. Fails if a snippet with the same id already exists.
+    fn insert(&mut self, snippet: Snippet) -> Result<(), StoreError>;
+
+    /// Replaces the snippet with the same id.
+    fn update(&mut self, snippet: Snippet) -> Result<(), StoreError>;
+
+    /// Moves a snippet to the trash. It keeps its place in the list, so
+    /// restoring it puts it back where it was.
+    fn trash(&mut self, id: &str) -> Result<(), StoreError>;
+
+    /// Takes a snippet back out of the trash.
+    fn restore(&mut self, id: &str) -> Result<(), StoreError>;
+
+    /// Removes a snippet for good and hands it back.
+    fn delete(&mut self, id: &str) -> Result<Snippet, StoreError>;
+
+    /// Permanently deletes everything trashed before `cutoff` and returns
+    /// how many snippets were purged.
+    fn purge_trashed_before(&mut self, cutoff: DateTime<Utc>) -> Result<usize, StoreError>;
+
+    /// Flips the favorite flag and returns the new value.
+    fn toggle_favorite(&mut self, id: &str) -> Result<bool, StoreError>;
+
+    /// Counts one view or copy of a snippet. Only the counter changes, so
+    /// the search index is left alone.
+    fn record_use(&mut self, id: &str, usage: Usage) -> Result<(), StoreError>;
+}
+
+/// What [`SnippetStore::record_use`] counts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Usage {
+    View,
+    Copy,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StoreError {
+    NotFound(String),
+    DuplicateId(String),
+    /// The backend could not be read or written.
+    Backend(String),
+    /// Saved data exists but could not be decoded or upgraded.
+    Schema(SchemaError),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::NotFound(id) => write!(f, "no snippet with id {id}"),
+            StoreError::DuplicateId(id) => write!(f, "a snippet with id {id} already exists"),
+            StoreError::Backend(msg) => write!(f, "storage error: {msg}"),
+            StoreError::Schema(err) => write!(f, "saved data could not be read: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<SchemaError> for StoreError {
+    fn from(err: SchemaError) -> Self {
+        StoreError::Schema(err)
+    }
+}
+
+/// The platform's backend for the document called `name`, next to the vault:
+/// a `localStorage` key on the web and `<name>.json` in the app-data
+/// directory elsewhere.
+pub fn default_backend(name: &str) -> impl Backend {
+    #[cfg(target_arch = "wasm32")]
+    return LocalStorage::new(format!("code-snippet-vault.{name}"));
+    #[cfg(not(target_arch = "wasm32"))]
+    return JsonFile::in_data_dir(&format!("{name}.json"));
+}
+
+/// Opens the platform's persistent store, seeding it with `seed` on first run.
+///
+/// If the saved vault cannot be opened the error is returned alongside an
+/// empty in-memory store, so the saved data is left untouched rather than
+/// overwritten and the UI can tell the user what happened.
+pub fn open_default(
+    seed: impl FnOnce() -> Vec<Snippet>,
+) -> (Box<dyn SnippetStore>, Option<StoreError>) {
+    #[cfg(target_arch = "wasm32")]
+    let opened = WebStore::open(LocalStorage::default(), seed);
+    #[cfg(not(target_arch = "wasm32"))]
+    let opened = FileStore::open(JsonFile::default(), seed);
+
+    match opened {
+        Ok(store) => (Box::new(store), None),
+        Err(err) => {
+            tracing::error!("{err}; changes will not be saved this session");
+            (Box::new(MemoryStore::default()), Some(err))
+        }
+    }
+}
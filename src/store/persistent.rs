@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+
+use crate::search::index::SearchIndex;
+use crate::Snippet;
+
+use super::{schema, MemoryStore, SnippetStore, StoreError, Usage};
+
+/// Somewhere the serialized vault can be kept between sessions.
+pub trait Backend {
+    /// The saved vault, or `None` if nothing has been saved yet.
+    fn read(&self) -> Result<Option<String>, StoreError>;
+
+    fn write(&self, raw: &str) -> Result<(), StoreError>;
+}
+
+/// A [`MemoryStore`] that writes the full vault to its backend after every
+/// change.
+///
+/// A failed write is reported to the caller but the change is kept in
+/// memory, so the UI never shows something different from the store.
+pub struct PersistentStore<B: Backend> {
+    backend: B,
+    inner: MemoryStore,
+}
+
+impl<B: Backend> PersistentStore<B> {
+    /// Loads the vault from `backend`, or saves `seed()` to it on first run.
+    pub fn open(backend: B, seed: impl FnOnce() -> Vec<Snippet>) -> Result<Self, StoreError> {
+        let (snippets, first_run) = match backend.read()? {
+            Some(raw) => (schema::decode(&raw)?, false),
+            None => (seed(), true),
+        };
+        let store = Self {
+            backend,
+            inner: MemoryStore::new(snippets),
+        };
+        if first_run {
+            store.persist()?;
+        }
+        Ok(store)
+    }
+
+    fn persist(&self) -> Result<(), StoreError> {
+        let raw = schema::encode(self.inner.list())
+            .map_err(|err| StoreError::Backend(err.to_string()))?;
+        self.backend.write(&raw)
+    }
+}
+
+impl<B: Backend> SnippetStore for PersistentStore<B> {
+    fn list(&self) -> &[Snippet] {
+        self.inner.list()
+    }
+
+    fn get(&self, id: &str) -> Option<&Snippet> {
+        self.inner.get(id)
+    }
+
+    fn search_index(&self) -> &SearchIndex {
+        self.inner.search_index()
+    }
+
+    fn insert(&mut self, snippet: Snippet) -> Result<(), StoreError> {
+        self.inner.insert(snippet)?;
+        self.persist()
+    }
+
+    fn update(&mut self, snippet: Snippet) -> Result<(), StoreError> {
+        self.inner.update(snippet)?;
+        self.persist()
+    }
+
+    fn trash(&mut self, id: &str) -> Result<(), StoreError> {
+        self.inner.trash(id)?;
+        self.persist()
+    }
+
+    fn restore(&mut self, id: &str) -> Result<(), StoreError> {
+        self.inner.restore(id)?;
+        self.persist()
+    }
+
+    fn delete(&mut self, id: &str) -> Result<Snippet, StoreError> {
+        let snippet = self.inner.delete(id)?;
+        self.persist()?;
+        Ok(snippet)
+    }
+
+    fn purge_trashed_before(&mut self, cutoff: DateTime<Utc>) -> Result<usize, StoreError> {
+        let purged = self.inner.purge_trashed_before(cutoff)?;
+        if purged > 0 {
+            self.persist()?;
+        }
+        Ok(purged)
+    }
+
+    fn toggle_favorite(&mut self, id: &str) -> Result<bool, StoreError> {
+        let is_favorite = self.inner.toggle_favorite(id)?;
+        self.persist()?;
+        Ok(is_favorite)
+    }
+
+    fn record_use(&mut self, id: &str, usage: Usage) -> Result<(), StoreError> {
+        self.inner.record_use(id, usage)?;
+        self.persist()
+    }
+}
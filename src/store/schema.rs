@@ -0,0 +1,307 @@
+//! The on-disk format of the vault and the documents saved next to it.
+//!
+//! Each document is saved as an envelope `{ "version": N, "<field>": ... }`,
+//! the vault as `{ "version": N, "snippets": [...] }`. Loading upgrades
+//! older envelopes one version at a time through the document's
+//! [`Format::migrations`] before deserializing the payload, so a field added
+//! to [`Snippet`] only needs a new migration step rather than breaking
+//! existing vaults.
+//!
+//! Purely additive fields that carry `#[serde(default)]` do not need a new
+//! version.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::Snippet;
+
+/// Upgrades an envelope from one version to the next.
+pub type Migration = fn(Value) -> Result<Value, String>;
+
+/// How one document is versioned.
+pub struct Format {
+    /// The envelope key holding the payload.
+    pub field: &'static str,
+    /// `migrations[n]` upgrades a version `n` envelope to version `n + 1`,
+    /// so there is one per version the format has been through.
+    ///
+    /// Version 0 is whatever was saved before the document had an
+    /// envelope; it arrives wrapped as `{ "version": 0, "<field>": ... }`.
+    pub migrations: &'static [Migration],
+}
+
+/// The version of the vault written by this build.
+pub const CURRENT_VERSION: u32 = 3;
+
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize] = [v0_to_v1, v1_to_v2, v2_to_v3];
+
+pub const VAULT: Format = Format { field: "snippets", migrations: &MIGRATIONS };
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaError {
+    /// Not JSON, or not shaped like any version of the document.
+    Malformed(String),
+    /// Written by a newer build of the app than this one.
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// A migration step rejected the payload.
+    Migration { from: u32, reason: String },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Malformed(msg) => write!(f, "malformed data: {msg}"),
+            SchemaError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "format version {found} is newer than this app supports (version {supported})"
+            ),
+            SchemaError::Migration { from, reason } => {
+                write!(f, "could not upgrade from version {from}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl Format {
+    /// The version written by this build.
+    pub fn version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(&self, payload: &T) -> serde_json::Result<String> {
+        let mut envelope = Map::new();
+        envelope.insert("version".into(), self.version().into());
+        envelope.insert(self.field.into(), serde_json::to_value(payload)?);
+        serde_json::to_string(&envelope)
+    }
+
+    /// Decodes a saved document of any known version.
+    pub fn decode<T: DeserializeOwned>(&self, raw: &str) -> Result<T, SchemaError> {
+        let value: Value =
+            serde_json::from_str(raw).map_err(|err| SchemaError::Malformed(err.to_string()))?;
+
+        let (mut version, mut value) = match version_of(&value)? {
+            Some(version) => (version, value),
+            None => (0, serde_json::json!({ "version": 0, self.field: value })),
+        };
+        if version > self.version() {
+            return Err(SchemaError::UnsupportedVersion { found: version, supported: self.version() });
+        }
+        while version < self.version() {
+            value = self.migrations[version as usize](value)
+                .map_err(|reason| SchemaError::Migration { from: version, reason })?;
+            version += 1;
+        }
+
+        let payload = value
+            .get_mut(self.field)
+            .map(Value::take)
+            .ok_or_else(|| SchemaError::Malformed(format!("missing \"{}\"", self.field)))?;
+        serde_json::from_value(payload).map_err(|err| SchemaError::Malformed(err.to_string()))
+    }
+}
+
+/// The first step of a document whose payload stayed the same when it was
+/// put in an envelope.
+pub fn enveloped(value: Value) -> Result<Value, String> {
+    Ok(value)
+}
+
+pub fn encode(snippets: &[Snippet]) -> serde_json::Result<String> {
+    VAULT.encode(snippets)
+}
+
+/// Decodes a saved vault of any known version.
+pub fn decode(raw: &str) -> Result<Vec<Snippet>, SchemaError> {
+    VAULT.decode(raw)
+}
+
+/// The envelope's version, or `None` for a document saved before it had
+/// one: anything but an object with a `"version"` key.
+fn version_of(value: &Value) -> Result<Option<u32>, SchemaError> {
+    let Some(version) = value.get("version").filter(|_| value.is_object()) else {
+        return Ok(None);
+    };
+    version
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .map(Some)
+        .ok_or_else(|| SchemaError::Malformed("invalid \"version\"".into()))
+}
+
+/// Version 0 is the bare snippet array saved before the envelope existed.
+fn v0_to_v1(value: Value) -> Result<Value, String> {
+    if !value["snippets"].is_array() {
+        return Err("expected an array of snippets".into());
+    }
+    Ok(value)
+}
+
+/// Version 1 stored `created_at`/`updated_at` as free-form strings, in
+/// practice plain dates such as `"2024-01-15"`. Version 2 stores RFC 3339.
+fn v1_to_v2(mut value: Value) -> Result<Value, String> {
+    let snippets = value
+        .get_mut("snippets")
+        .and_then(Value::as_array_mut)
+        .ok_or("missing \"snippets\" array")?;
+    for snippet in snippets {
+        for field in ["created_at", "updated_at"] {
+            let Some(slot) = snippet.get_mut(field) else {
+                continue;
+            };
+            let old = slot.as_str().ok_or_else(|| format!("{field} is not a string"))?;
+            *slot = Value::String(upgrade_timestamp(old)?);
+        }
+    }
+    value["version"] = 2.into();
+    Ok(value)
+}
+
+fn upgrade_timestamp(old: &str) -> Result<String, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(old) {
+        return Ok(at.to_utc().to_rfc3339());
+    }
+    NaiveDate::parse_from_str(old, "%Y-%m-%d")
+        .map(|date| date.and_time(Default::default()).and_utc().to_rfc3339())
+        .map_err(|_| format!("unrecognized timestamp {old:?}"))
+}
+
+/// Version 2 ids were millisecond timestamps, which collide when two
+/// snippets are created in the same millisecond. Existing ids are kept so
+/// saved links still resolve; only later duplicates get a fresh id.
+fn v2_to_v3(mut value: Value) -> Result<Value, String> {
+    let snippets = value
+        .get_mut("snippets")
+        .and_then(Value::as_array_mut)
+        .ok_or("missing \"snippets\" array")?;
+    let mut seen = HashSet::new();
+    for snippet in snippets {
+        let id = match snippet.get("id") {
+            Some(Value::String(id)) => id.clone(),
+            Some(Value::Number(id)) => id.to_string(),
+            _ => return Err("snippet without an id".into()),
+        };
+        let id = if seen.contains(&id) { Snippet::new_id() } else { id };
+        seen.insert(id.clone());
+        snippet["id"] = Value::String(id);
+    }
+    value["version"] = 3.into();
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// A snippet as the first versions saved it: plain-date timestamps and,
+    /// before version 3, possibly numeric ids.
+    fn old_snippet(id: Value, title: &str) -> Value {
+        json!({
+            "id": id,
+            "title": title,
+            "language": "Rust",
+            "code": "fn main() {}",
+            "description": null,
+            "tags": ["cli"],
+            "created_at": "2024-01-15",
+            "updated_at": "2024-01-16",
+            "is_favorite": false,
+        })
+    }
+
+    #[test]
+    fn decodes_a_bare_v0_array() {
+        let raw = json!([old_snippet(json!("1"), "Hello"), old_snippet(json!("2"), "World")]);
+        let snippets = decode(&raw.to_string()).unwrap();
+        let titles: Vec<&str> = snippets.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Hello", "World"]);
+        assert_eq!(snippets[0].created_at.to_rfc3339(), "2024-01-15T00:00:00+00:00");
+    }
+
+    #[test]
+    fn upgrades_v1_plain_dates_and_keeps_rfc3339() {
+        let mut offset = old_snippet(json!("2"), "Offset");
+        offset["updated_at"] = json!("2024-03-01T12:00:00+02:00");
+        let raw = json!({ "version": 1, "snippets": [old_snippet(json!("1"), "Plain"), offset] });
+        let snippets = decode(&raw.to_string()).unwrap();
+        assert_eq!(snippets[0].updated_at.to_rfc3339(), "2024-01-16T00:00:00+00:00");
+        assert_eq!(snippets[1].updated_at.to_rfc3339(), "2024-03-01T10:00:00+00:00");
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_v1_date() {
+        let mut snippet = old_snippet(json!("1"), "Bad");
+        snippet["created_at"] = json!("last tuesday");
+        let raw = json!({ "version": 1, "snippets": [snippet] });
+        assert!(matches!(decode(&raw.to_string()), Err(SchemaError::Migration { from: 1, .. })));
+    }
+
+    #[test]
+    fn gives_colliding_v2_ids_fresh_ones_but_keeps_the_first() {
+        let mut snippets: Vec<Value> = [1700000000000_u64, 1700000000000, 1700000000001]
+            .into_iter()
+            .map(|id| old_snippet(json!(id), "Same millisecond"))
+            .collect();
+        for snippet in &mut snippets {
+            snippet["created_at"] = json!("2024-01-15T00:00:00+00:00");
+            snippet["updated_at"] = json!("2024-01-15T00:00:00+00:00");
+        }
+        let raw = json!({ "version": 2, "snippets": snippets });
+        let decoded = decode(&raw.to_string()).unwrap();
+        assert_eq!(decoded[0].id, "1700000000000");
+        assert_ne!(decoded[1].id, "1700000000000");
+        assert_eq!(decoded[2].id, "1700000000001");
+    }
+
+    #[test]
+    fn unversioned_documents_go_through_every_migration() {
+        const LIST: Format = Format { field: "names", migrations: &[enveloped, sorted] };
+        fn sorted(mut value: Value) -> Result<Value, String> {
+            let names = value["names"].as_array_mut().ok_or("not a list")?;
+            names.sort_by_key(|name| name.as_str().map(str::to_string));
+            Ok(value)
+        }
+
+        let names: Vec<String> = LIST.decode(r#"["b", "a"]"#).unwrap();
+        assert_eq!(names, ["a", "b"]);
+        let saved = LIST.encode(&names).unwrap();
+        assert_eq!(saved, r#"{"names":["a","b"],"version":2}"#);
+        assert_eq!(LIST.decode::<Vec<String>>(&saved).unwrap(), names);
+    }
+
+    #[test]
+    fn round_trips_the_current_version() {
+        let snippets = vec![Snippet::for_test("a", "Current", "let x = 1;")];
+        assert_eq!(decode(&encode(&snippets).unwrap()).unwrap(), snippets);
+    }
+
+    #[test]
+    fn refuses_a_newer_version() {
+        let raw = json!({ "version": CURRENT_VERSION + 1, "snippets": [] });
+        let error = SchemaError::UnsupportedVersion { found: CURRENT_VERSION + 1, supported: CURRENT_VERSION };
+        assert_eq!(decode(&raw.to_string()), Err(error));
+    }
+
+    #[test]
+    fn malformed_input_is_an_error_not_an_empty_vault() {
+        for raw in ["", "not json", r#"{"version": "3", "snippets": []}"#, r#"{"version": 3}"#] {
+            assert!(matches!(decode(raw), Err(SchemaError::Malformed(_))), "{raw:?}");
+        }
+        for raw in ["42", r#"{"snippets": []}"#] {
+            assert!(matches!(decode(raw), Err(SchemaError::Migration { from: 0, .. })), "{raw:?}");
+        }
+        let missing_field = json!({ "version": CURRENT_VERSION, "snippets": [{ "id": "1" }] });
+        assert!(matches!(decode(&missing_field.to_string()), Err(SchemaError::Malformed(_))));
+        let no_snippets = json!({ "version": 1 });
+        assert!(matches!(decode(&no_snippets.to_string()), Err(SchemaError::Migration { from: 1, .. })));
+    }
+}
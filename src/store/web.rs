@@ -0,0 +1,40 @@
+use super::{Backend, PersistentStore, StoreError};
+
+/// The vault as a single JSON document in the browser's `localStorage`.
+pub type WebStore = PersistentStore<LocalStorage>;
+
+pub struct LocalStorage {
+    key: String,
+}
+
+impl LocalStorage {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into() }
+    }
+
+    fn storage() -> Result<web_sys::Storage, StoreError> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| StoreError::Backend("localStorage is unavailable".into()))
+    }
+}
+
+impl Default for LocalStorage {
+    fn default() -> Self {
+        Self::new("code-snippet-vault.snippets")
+    }
+}
+
+impl Backend for LocalStorage {
+    fn read(&self) -> Result<Option<String>, StoreError> {
+        Self::storage()?
+            .get_item(&self.key)
+            .map_err(|err| StoreError::Backend(format!("{err:?}")))
+    }
+
+    fn write(&self, raw: &str) -> Result<(), StoreError> {
+        Self::storage()?
+            .set_item(&self.key, raw)
+            .map_err(|err| StoreError::Backend(format!("{err:?}")))
+    }
+}
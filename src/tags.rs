@@ -0,0 +1,244 @@
+//! Vault-wide tag bookkeeping: usage counts, renames, normalization and
+//! the tag hierarchy.
+//!
+//! A tag like `web/react/hooks` sits under `web/react`, which sits under
+//! `web`. The hierarchy lives entirely in the tag text, so it needs nothing
+//! extra in the saved vault.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use crate::history;
+use crate::settings::TagRules;
+use crate::Snippet;
+
+/// Separates the levels of a hierarchical tag.
+pub const LEVEL_SEPARATOR: char = '/';
+
+/// `tag` cleaned up by `rules`, or `None` if nothing is left of it.
+///
+/// Surrounding whitespace and a leading `#` go, inner runs of whitespace
+/// become `rules.space_replacement`, and the tag is lowercased if asked.
+/// Each level of a hierarchical tag is cleaned up on its own, and empty
+/// levels are dropped, so `Web / React//` becomes `web/react`.
+pub fn normalize(tag: &str, rules: &TagRules) -> Option<String> {
+    let joiner = if rules.space_replacement.is_empty() { " " } else { rules.space_replacement.as_str() };
+    let levels: Vec<String> = tag
+        .trim()
+        .trim_start_matches('#')
+        .split(LEVEL_SEPARATOR)
+        .map(|level| level.split_whitespace().collect::<Vec<_>>().join(joiner))
+        .filter(|level| !level.is_empty())
+        .collect();
+    let tag = levels.join(&LEVEL_SEPARATOR.to_string());
+    let tag = if rules.lowercase { tag.to_lowercase() } else { tag };
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Whether `tag` is `ancestor` or nested anywhere below it, ignoring ASCII case.
+pub fn is_within(tag: &str, ancestor: &str) -> bool {
+    match tag.get(..ancestor.len()) {
+        Some(head) if head.eq_ignore_ascii_case(ancestor) => {
+            tag[ancestor.len()..].is_empty() || tag[ancestor.len()..].starts_with(LEVEL_SEPARATOR)
+        }
+        _ => false,
+    }
+}
+
+/// The last level of `tag`: `hooks` for `web/react/hooks`.
+pub fn leaf(tag: &str) -> &str {
+    tag.rsplit(LEVEL_SEPARATOR).next().unwrap_or(tag)
+}
+
+/// One level of the tag hierarchy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagNode {
+    /// This level alone, e.g. `react`.
+    pub name: String,
+    /// The full tag up to this level, e.g. `web/react`.
+    pub path: String,
+    /// Live snippets tagged with this path or anything below it.
+    pub count: usize,
+    pub children: Vec<TagNode>,
+}
+
+/// The tag hierarchy of the snippets outside the trash, each level sorted
+/// by name.
+pub fn tree<'a>(snippets: impl IntoIterator<Item = &'a Snippet>) -> Vec<TagNode> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for snippet in snippets.into_iter().filter(|snippet| !snippet.is_trashed()) {
+        // A snippet counts once per path, however many of its tags share it.
+        let mut paths = HashSet::new();
+        for tag in &snippet.tags {
+            let mut path = String::new();
+            for level in tag.split(LEVEL_SEPARATOR).filter(|level| !level.is_empty()) {
+                if !path.is_empty() {
+                    path.push(LEVEL_SEPARATOR);
+                }
+                path.push_str(level);
+                paths.insert(path.clone());
+            }
+        }
+        for path in paths {
+            *counts.entry(path).or_default() += 1;
+        }
+    }
+    children_of(&counts, "")
+}
+
+fn children_of(counts: &BTreeMap<String, usize>, parent: &str) -> Vec<TagNode> {
+    counts
+        .iter()
+        .filter_map(|(path, count)| {
+            let name = if parent.is_empty() {
+                path.as_str()
+            } else {
+                path.strip_prefix(parent)?.strip_prefix(LEVEL_SEPARATOR)?
+            };
+            (!name.contains(LEVEL_SEPARATOR)).then(|| TagNode {
+                name: name.to_string(),
+                path: path.clone(),
+                count: *count,
+                children: children_of(counts, path),
+            })
+        })
+        .collect()
+}
+
+/// Splits `input` at `rules.separators`, returning the finished tags,
+/// normalized, and the unfinished text after the last separator.
+///
+/// [`LEVEL_SEPARATOR`] never splits, even in settings saved before that was
+/// enforced, so hierarchical tags can always be typed.
+pub fn split<'a>(input: &'a str, rules: &TagRules) -> (Vec<String>, &'a str) {
+    let is_separator = |c: char| c != LEVEL_SEPARATOR && rules.separators.contains(c);
+    let (finished, rest) = match input.rfind(is_separator) {
+        Some(at) => {
+            let separator_len = input[at..].chars().next().map_or(1, char::len_utf8);
+            (&input[..at], &input[at + separator_len..])
+        }
+        None => ("", input),
+    };
+    let tags = finished.split(is_separator).filter_map(|tag| normalize(tag, rules)).collect();
+    (tags, rest)
+}
+
+/// Every tag with the number of snippets carrying it, most used first and
+/// then alphabetically. Trashed snippets count, since renames reach them too.
+pub fn usage(snippets: &[Snippet]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for tag in snippets.iter().flat_map(|snippet| &snippet.tags) {
+        *counts.entry(tag.as_str()).or_default() += 1;
+    }
+    let mut usage: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(tag, count)| (tag.to_string(), count))
+        .collect();
+    usage.sort_by(|(_, a), (_, b)| b.cmp(a));
+    usage
+}
+
+/// Replaces every tag in `from` with `to`, or drops them when `to` is
+/// `None`, returning only the snippets that changed.
+///
+/// Rename, merge and delete are all this one operation. Renaming carries
+/// the tags nested below along, so `web` to `frontend` turns `web/react`
+/// into `frontend/react`; deleting only drops the exact tags. A snippet
+/// that ends up with a tag twice keeps it once, in the place of the first.
+/// Each changed snippet counts as saved at `now` and gets a revision for it.
+pub fn retag(snippets: &[Snippet], from: &[String], to: Option<&str>, now: DateTime<Utc>) -> Vec<Snippet> {
+    snippets
+        .iter()
+        .filter_map(|snippet| {
+            let mut tags: Vec<String> = Vec::with_capacity(snippet.tags.len());
+            for tag in &snippet.tags {
+                let tag = match to {
+                    Some(to) => renamed(tag, from, to).unwrap_or_else(|| tag.clone()),
+                    None if from.contains(tag) => continue,
+                    None => tag.clone(),
+                };
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+            if tags == snippet.tags {
+                return None;
+            }
+            let mut retagged = Snippet { tags, updated_at: now, ..snippet.clone() };
+            history::record_save(Some(snippet), &mut retagged);
+            Some(retagged)
+        })
+        .collect()
+}
+
+/// `tag` with the longest of `from` that it is, or is nested below,
+/// replaced by `to`; `None` if it is under none of them.
+fn renamed(tag: &str, from: &[String], to: &str) -> Option<String> {
+    from.iter()
+        .filter_map(|from| tag.strip_prefix(from.as_str()).map(|rest| (from.len(), rest)))
+        .filter(|(_, rest)| rest.is_empty() || rest.starts_with(LEVEL_SEPARATOR))
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, rest)| format!("{to}{rest}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time;
+
+    fn tagged(tags: &[&str]) -> Snippet {
+        let mut snippet = Snippet::for_test("1", "Hooks", "useEffect(() => {});");
+        snippet.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        snippet
+    }
+
+    #[test]
+    fn retag_records_a_revision_with_the_new_tags() {
+        let snippet = tagged(&["js", "react"]);
+        let now = time::now();
+        let changed = retag(&[snippet], &["js".to_string()], Some("javascript"), now);
+        let retagged = &changed[0];
+        assert_eq!(retagged.tags, ["javascript", "react"]);
+        assert_eq!(retagged.updated_at, now);
+        assert_eq!(retagged.history.len(), 2);
+        assert_eq!(retagged.history[0].tags, ["js", "react"]);
+        assert_eq!(retagged.history.last().unwrap().tags, retagged.tags);
+    }
+
+    #[test]
+    fn renaming_carries_nested_tags_along() {
+        let snippet = tagged(&["web", "web/react/hooks", "website"]);
+        let changed = retag(&[snippet], &["web".to_string()], Some("frontend"), time::now());
+        assert_eq!(changed[0].tags, ["frontend", "frontend/react/hooks", "website"]);
+    }
+
+    #[test]
+    fn merging_uses_the_closest_merged_ancestor() {
+        let snippet = tagged(&["web/react/hooks", "web/vue"]);
+        let from = ["web".to_string(), "web/react".to_string()];
+        let changed = retag(&[snippet], &from, Some("ui"), time::now());
+        assert_eq!(changed[0].tags, ["ui/hooks", "ui/vue"]);
+    }
+
+    #[test]
+    fn deleting_keeps_nested_tags() {
+        let snippet = tagged(&["web", "web/react"]);
+        let changed = retag(&[snippet], &["web".to_string()], None, time::now());
+        assert_eq!(changed[0].tags, ["web/react"]);
+    }
+
+    #[test]
+    fn level_separator_never_splits_tags() {
+        let rules = TagRules { separators: ",/".into(), ..TagRules::default() };
+        let (finished, rest) = split("Web/React, rust,as", &rules);
+        assert_eq!(finished, ["web/react", "rust"]);
+        assert_eq!(rest, "as");
+    }
+
+    #[test]
+    fn retag_leaves_untouched_snippets_out() {
+        let changed = retag(&[tagged(&["rust"])], &["js".to_string()], None, time::now());
+        assert!(changed.is_empty());
+    }
+}